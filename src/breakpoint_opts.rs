@@ -0,0 +1,327 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_breakpoint_opts {
+    use crate::debug_opts::m_debug_opts::parse_number;
+    use crate::dwarf_info::m_dwarf_info::DwarfInfo;
+    use crate::elf_symbols::m_elf_symbols::ElfSymbols;
+    use crate::probe_rs_invoke::probe_rs_integration::ProbeRsHandler;
+    use probe_rs::{CoreStatus, HaltReason};
+    use std::path::Path;
+
+    /// Resolves an address, a `file:line` or a symbol name typed by the user.
+    pub fn resolve_location(
+        text: &str,
+        symbols: &ElfSymbols,
+        dwarf: &DwarfInfo,
+    ) -> Result<u64, String> {
+        let text = text.trim();
+        if let Some(a) = parse_number(text) {
+            return u64::try_from(a).map_err(|e| format!("{}", e));
+        }
+        if let Some((file, line)) = text.rsplit_once(':') {
+            if let Ok(line) = line.trim().parse::<u64>() {
+                return dwarf
+                    .address_of_line(file, line)
+                    .ok_or(format!("no code found for {}", text));
+            }
+        }
+        symbols
+            .lookup(text)
+            .map(|s| s.address)
+            .ok_or(format!("unknown address or symbol: {}", text))
+    }
+
+    /// Describes `address` as `symbol+offset (file:line)` when the ELF knows it.
+    pub fn describe_address(address: u64, symbols: &ElfSymbols, dwarf: &DwarfInfo) -> String {
+        let mut s = String::new();
+        if let Some((sym, offset)) = symbols.symbol_at(address) {
+            s = if offset == 0 {
+                sym.name.clone()
+            } else {
+                format!("{}+{:#x}", sym.name, offset)
+            };
+        }
+        if let Some((file, line)) = dwarf.location_at(address) {
+            let file = Path::new(file)
+                .file_name()
+                .map_or(file.into(), |f| f.to_string_lossy());
+            s = format!("{} ({}:{})", s, file, line);
+        }
+        s
+    }
+
+    struct Breakpoint {
+        core_idx: usize,
+        location: String,
+        address: u64,
+        enabled: bool,
+        hits: usize,
+    }
+
+    #[derive(Default)]
+    pub struct BreakpointView {
+        breakpoints: Vec<Breakpoint>,
+        new_location: String,
+        /// Comparators of a core, cached per session and core index.
+        units: Option<(usize, usize, u32)>,
+        /// Session the enabled breakpoints were programmed under.
+        session_id: usize,
+        cores_halted: Vec<bool>,
+        rst_info: Option<String>,
+    }
+
+    impl BreakpointView {
+        fn used_units(&self, core_idx: usize) -> usize {
+            self.breakpoints
+                .iter()
+                .filter(|b| b.core_idx == core_idx && b.enabled)
+                .count()
+        }
+
+        /// The comparators are lost with the session, breakpoints programmed under
+        /// another one are shown disabled again.
+        fn sync_session(&mut self, h: &ProbeRsHandler) {
+            if h.session.is_none() || h.session_id != self.session_id {
+                for b in self.breakpoints.iter_mut() {
+                    b.enabled = false;
+                }
+                self.session_id = h.session_id;
+            }
+        }
+
+        /// Counts a hit on every breakpoint the cores just halted on.
+        pub fn poll(&mut self, h: &mut ProbeRsHandler, cores_status: &[Option<CoreStatus>]) {
+            self.sync_session(h);
+            self.cores_halted.resize(cores_status.len(), false);
+            for (c, status) in cores_status.iter().enumerate() {
+                let b_halted = status.is_some_and(|s| s.is_halted());
+                let b_new_halt = b_halted && !self.cores_halted[c];
+                self.cores_halted[c] = b_halted;
+                if !b_new_halt
+                    || !matches!(status, Some(CoreStatus::Halted(HaltReason::Breakpoint(_))))
+                {
+                    continue;
+                }
+                if let Ok(Some(pc)) = h.read_pc(c) {
+                    for b in self.breakpoints.iter_mut() {
+                        if b.core_idx == c && b.enabled && b.address == pc {
+                            b.hits = b.hits.wrapping_add(1);
+                        }
+                    }
+                }
+            }
+        }
+
+        fn enable(&mut self, h: &mut ProbeRsHandler, idx: usize, b_enable: bool) {
+            let Some(b) = self.breakpoints.get(idx) else {
+                return;
+            };
+            let (core_idx, address) = (b.core_idx, b.address);
+            if b_enable {
+                if let Some((_, c, units)) = self.units {
+                    if c == core_idx && self.used_units(core_idx) >= units as usize {
+                        self.rst_info =
+                            Some(format!("all {} breakpoint comparators are in use", units));
+                        return;
+                    }
+                }
+            }
+            let rst = if b_enable {
+                h.set_breakpoint(core_idx, address)
+            } else {
+                h.clear_breakpoint(core_idx, address)
+            };
+            match rst {
+                Ok(_) => {
+                    self.breakpoints[idx].enabled = b_enable;
+                    self.rst_info.take();
+                }
+                Err(e) => {
+                    self.rst_info = Some(format!("{}", e));
+                }
+            }
+        }
+
+        fn refresh_units(&mut self, h: &mut ProbeRsHandler, core_idx: usize) {
+            self.sync_session(h);
+            if h.session.is_none() {
+                self.units = None;
+                return;
+            }
+            if self
+                .units
+                .is_none_or(|(s, c, _)| s != h.session_id || c != core_idx)
+            {
+                self.units = h
+                    .breakpoint_units(core_idx)
+                    .ok()
+                    .flatten()
+                    .map(|u| (h.session_id, core_idx, u));
+            }
+        }
+
+        fn add(&mut self, h: &mut ProbeRsHandler, core_idx: usize, location: String, address: u64) {
+            self.breakpoints.push(Breakpoint {
+                core_idx,
+                location,
+                address,
+                enabled: false,
+                hits: 0,
+            });
+            self.enable(h, self.breakpoints.len() - 1, true);
+        }
+
+        fn remove(&mut self, h: &mut ProbeRsHandler, idx: usize) {
+            if self.breakpoints[idx].enabled {
+                self.enable(h, idx, false);
+            }
+            if !self.breakpoints[idx].enabled {
+                self.breakpoints.remove(idx);
+            }
+        }
+
+        pub fn is_set(&self, core_idx: usize, address: u64) -> bool {
+            self.breakpoints
+                .iter()
+                .any(|b| b.core_idx == core_idx && b.address == address && b.enabled)
+        }
+
+        /// Adds a breakpoint at `address`, or removes the one already there. Returns
+        /// the error if the probe refused.
+        pub fn toggle_at(
+            &mut self,
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            address: u64,
+            location: String,
+        ) -> Option<String> {
+            self.refresh_units(h, core_idx);
+            match self
+                .breakpoints
+                .iter()
+                .position(|b| b.core_idx == core_idx && b.address == address)
+            {
+                Some(i) => self.remove(h, i),
+                None => self.add(h, core_idx, location, address),
+            }
+            self.rst_info.clone()
+        }
+
+        pub fn ui(
+            &mut self,
+            ui: &mut eframe::egui::Ui,
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            symbols: &ElfSymbols,
+            dwarf: &DwarfInfo,
+        ) {
+            self.refresh_units(h, core_idx);
+
+            ui.horizontal(|ui| {
+                let resp = ui.add(
+                    eframe::egui::TextEdit::singleline(&mut self.new_location)
+                        .hint_text("address, function or file:line")
+                        .desired_width(240.0),
+                );
+                let b_enter =
+                    resp.lost_focus() && ui.input(|i| i.key_pressed(eframe::egui::Key::Enter));
+                if (ui.button("add breakpoint").clicked() || b_enter)
+                    && !self.new_location.is_empty()
+                {
+                    match resolve_location(&self.new_location, symbols, dwarf) {
+                        Ok(address) => {
+                            if self
+                                .breakpoints
+                                .iter()
+                                .any(|b| b.core_idx == core_idx && b.address == address)
+                            {
+                                self.rst_info =
+                                    Some(format!("breakpoint at {:#010x} already exists", address));
+                            } else {
+                                let location = self.new_location.trim().to_owned();
+                                self.new_location.clear();
+                                self.add(h, core_idx, location, address);
+                            }
+                        }
+                        Err(e) => {
+                            self.rst_info = Some(e);
+                        }
+                    }
+                }
+                match self.units {
+                    Some((_, _, units)) => {
+                        ui.label(format!(
+                            "comparators: {}/{}",
+                            self.used_units(core_idx),
+                            units
+                        ));
+                    }
+                    None => {
+                        ui.label("comparators: -");
+                    }
+                }
+            });
+            if let Some(info) = self.rst_info.as_ref() {
+                ui.label(info);
+            }
+
+            let mut toggle = None;
+            let mut remove_idx = None;
+            eframe::egui::ScrollArea::vertical()
+                .auto_shrink(false)
+                .show(ui, |ui| {
+                    eframe::egui::Grid::new("debug_breakpoints")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label("");
+                            ui.label("location");
+                            ui.label("address");
+                            ui.label("resolved");
+                            ui.label("hits");
+                            ui.label("");
+                            ui.end_row();
+                            for (i, b) in self.breakpoints.iter().enumerate() {
+                                if b.core_idx != core_idx {
+                                    continue;
+                                }
+                                let mut enabled = b.enabled;
+                                if ui.checkbox(&mut enabled, "").changed() {
+                                    toggle = Some((i, enabled));
+                                }
+                                ui.label(&b.location);
+                                ui.monospace(format!("{:#010x}", b.address));
+                                ui.label(describe_address(b.address, symbols, dwarf));
+                                ui.label(format!("{}", b.hits));
+                                if ui.button("remove").clicked() {
+                                    remove_idx = Some(i);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+
+            if let Some((i, b_enable)) = toggle {
+                self.enable(h, i, b_enable);
+            }
+            if let Some(i) = remove_idx {
+                self.remove(h, i);
+            }
+        }
+    }
+}
@@ -0,0 +1,443 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_call_stack_opts {
+    use crate::configuration::m_config;
+    use crate::dwarf_info::m_dwarf_info::{DwarfInfo, FrameRegisters, TypeId};
+    use crate::elf_symbols::m_elf_symbols::ElfSymbols;
+    use crate::probe_rs_invoke::probe_rs_integration::ProbeRsHandler;
+    use crate::watch_opts::m_watch_opts::ValueRenderer;
+    use probe_rs::{CoreStatus, CoreType, RegisterRole};
+    use std::{collections::HashMap, path::Path};
+
+    const ARM_SP: u16 = 13;
+    const ARM_LR: u16 = 14;
+    const ARM_PC: u16 = 15;
+    const RISCV_SP: u16 = 2;
+    /// Any LR value at or above this is an EXC_RETURN on Cortex-M.
+    const EXC_RETURN_MIN: u64 = 0xFFFF_FF00;
+
+    /// The registers the core pushes on exception entry.
+    #[derive(Clone)]
+    pub struct ExceptionFrame {
+        /// Where the frame sits on the stack.
+        pub address: u64,
+        pub exc_return: u64,
+        pub r0: u64,
+        pub r1: u64,
+        pub r2: u64,
+        pub r3: u64,
+        pub r12: u64,
+        pub lr: u64,
+        pub pc: u64,
+        pub xpsr: u64,
+        /// Stack pointer value before the exception.
+        pub sp_before: u64,
+    }
+
+    /// Reads the Cortex-M exception frame at `sp`. `exc_return` tells whether the
+    /// frame carries the extended floating point state.
+    pub fn read_exception_frame(
+        read_word: &mut dyn FnMut(u64) -> Option<u64>,
+        sp: u64,
+        exc_return: u64,
+    ) -> Option<ExceptionFrame> {
+        let mut w = [0u64; 8];
+        for (i, v) in w.iter_mut().enumerate() {
+            *v = read_word(sp + i as u64 * 4)?;
+        }
+        let mut size = 0x20;
+        if exc_return & (1 << 4) == 0 {
+            size += 0x48;
+        }
+        if w[7] & (1 << 9) != 0 {
+            size += 4;
+        }
+        Some(ExceptionFrame {
+            address: sp,
+            exc_return,
+            r0: w[0],
+            r1: w[1],
+            r2: w[2],
+            r3: w[3],
+            r12: w[4],
+            lr: w[5],
+            pc: w[6],
+            xpsr: w[7],
+            sp_before: sp + size,
+        })
+    }
+
+    /// Maps a probe-rs register id to its DWARF register number.
+    fn dwarf_register(core_type: CoreType, id: u16) -> Option<u16> {
+        match core_type {
+            CoreType::Riscv if (0x1000..0x1020).contains(&id) => Some(id - 0x1000),
+            CoreType::Riscv | CoreType::Armv8a | CoreType::Xtensa => None,
+            _ if id <= ARM_PC => Some(id),
+            _ => None,
+        }
+    }
+
+    pub struct StackFrame {
+        pub pc: u64,
+        /// Address used for symbol and line lookups, i.e. the call site for callers.
+        pub lookup_pc: u64,
+        pub function: String,
+        pub location: Option<(String, u64)>,
+        pub registers: FrameRegisters,
+        /// Set when this frame was interrupted by an exception.
+        pub exception: Option<ExceptionFrame>,
+    }
+
+    fn describe_frame(
+        pc: u64,
+        lookup_pc: u64,
+        registers: FrameRegisters,
+        exception: Option<ExceptionFrame>,
+        symbols: &ElfSymbols,
+        dwarf: &DwarfInfo,
+    ) -> StackFrame {
+        let function = match dwarf.function_at(lookup_pc) {
+            Some(f) => f.name.clone(),
+            None => symbols
+                .symbol_at(lookup_pc)
+                .map_or("??".to_owned(), |(s, _)| s.name.clone()),
+        };
+        StackFrame {
+            pc,
+            lookup_pc,
+            function,
+            location: dwarf.location_at(lookup_pc).map(|(f, l)| (f.to_owned(), l)),
+            registers,
+            exception,
+        }
+    }
+
+    fn read_word(h: &mut ProbeRsHandler, core_idx: usize, size: u8, address: u64) -> Option<u64> {
+        let mut buf = vec![0u8; size as usize];
+        h.read_memory(core_idx, address, 1, &mut buf).ok()?;
+        let mut b = [0u8; 8];
+        b[..buf.len()].copy_from_slice(&buf);
+        Some(u64::from_le_bytes(b))
+    }
+
+    /// Walks the stack of a halted core using the ELF's call frame information,
+    /// stepping over Cortex-M exception frames.
+    pub fn unwind(
+        h: &mut ProbeRsHandler,
+        core_idx: usize,
+        symbols: &ElfSymbols,
+        dwarf: &DwarfInfo,
+    ) -> Result<Vec<StackFrame>, String> {
+        let core_type = h
+            .core_type(core_idx)
+            .map_err(|e| format!("{}", e))?
+            .ok_or("no core attached")?;
+        let snapshot = h
+            .read_core_registers(core_idx)
+            .map_err(|e| format!("{}", e))?;
+        let mut regs = FrameRegisters::default();
+        let mut pc = None;
+        let mut psp = None;
+        for (reg, value) in snapshot.iter() {
+            let Some(v) = (*value).map(|v| v as u64) else {
+                continue;
+            };
+            if reg.register_has_role(RegisterRole::ProgramCounter) {
+                pc = Some(v);
+            }
+            if reg.register_has_role(RegisterRole::ProcessStackPointer) {
+                psp = Some(v);
+            }
+            if let Some(n) = dwarf_register(core_type, reg.id().0) {
+                regs.regs.insert(n, v);
+            }
+        }
+        let mut pc = pc.ok_or("can't read the program counter")?;
+        let sp_reg = if core_type == CoreType::Riscv {
+            RISCV_SP
+        } else {
+            ARM_SP
+        };
+        let word_size = if dwarf.address_size == 0 {
+            4
+        } else {
+            dwarf.address_size
+        };
+
+        let mut frames = Vec::new();
+        let mut lookup_pc = pc;
+        let mut exception = None;
+        while frames.len() < m_config::CALL_STACK_MAX_FRAMES {
+            let mut rw = |a: u64| read_word(h, core_idx, word_size, a);
+            let step = dwarf.unwind_frame(&regs, lookup_pc, &mut rw);
+            if let Some(s) = step.as_ref() {
+                regs.cfa = Some(s.cfa);
+            }
+            frames.push(describe_frame(
+                pc,
+                lookup_pc,
+                regs.clone(),
+                exception.take(),
+                symbols,
+                dwarf,
+            ));
+
+            let (mut caller_regs, return_address) = match step {
+                Some(s) => {
+                    let mut r = s.regs;
+                    r.insert(sp_reg, s.cfa);
+                    let ra = r.get(&s.return_address_register).copied();
+                    (r, ra)
+                }
+                // Without CFI only a leaf function on ARM can be stepped out of.
+                None if frames.len() == 1 && core_type != CoreType::Riscv => {
+                    (regs.regs.clone(), regs.regs.get(&ARM_LR).copied())
+                }
+                None => break,
+            };
+            let Some(return_address) = return_address else {
+                break;
+            };
+
+            let caller_pc;
+            if core_type.is_cortex_m() && return_address >= EXC_RETURN_MIN {
+                // Bit 2 of EXC_RETURN selects the process stack.
+                let sp = if return_address & (1 << 2) != 0 {
+                    psp.ok_or("PSP not available")?
+                } else {
+                    *caller_regs.get(&ARM_SP).ok_or("SP not available")?
+                };
+                let mut rw = |a: u64| read_word(h, core_idx, 4, a);
+                let Some(f) = read_exception_frame(&mut rw, sp, return_address) else {
+                    break;
+                };
+                for (n, v) in [(0, f.r0), (1, f.r1), (2, f.r2), (3, f.r3), (12, f.r12)] {
+                    caller_regs.insert(n, v);
+                }
+                caller_regs.insert(ARM_LR, f.lr);
+                caller_regs.insert(ARM_SP, f.sp_before);
+                caller_pc = f.pc & !1;
+                lookup_pc = caller_pc;
+                exception = Some(f);
+            } else {
+                caller_pc = if core_type == CoreType::Riscv {
+                    return_address
+                } else {
+                    return_address & !1
+                };
+                // The return address is past the call, look up the call itself.
+                lookup_pc = caller_pc.saturating_sub(1);
+            }
+            if caller_pc == 0
+                || (caller_pc == pc && caller_regs.get(&sp_reg) == regs.regs.get(&sp_reg))
+            {
+                break;
+            }
+            if core_type != CoreType::Riscv {
+                caller_regs.insert(ARM_PC, caller_pc);
+            }
+            pc = caller_pc;
+            regs = FrameRegisters {
+                regs: caller_regs,
+                cfa: None,
+            };
+        }
+        Ok(frames)
+    }
+
+    struct LocalValue {
+        name: String,
+        type_id: Option<TypeId>,
+        value: Result<Vec<u8>, String>,
+    }
+
+    #[derive(Default)]
+    pub struct CallStackView {
+        frames: Vec<StackFrame>,
+        selected: usize,
+        locals: Vec<LocalValue>,
+        deref_cache: HashMap<(u64, usize), Option<Vec<u8>>>,
+        b_was_halted: bool,
+        rst_info: Option<String>,
+    }
+
+    impl CallStackView {
+        fn refresh(
+            &mut self,
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            symbols: &ElfSymbols,
+            dwarf: &DwarfInfo,
+        ) {
+            match unwind(h, core_idx, symbols, dwarf) {
+                Ok(frames) => {
+                    self.frames = frames;
+                    self.rst_info.take();
+                }
+                Err(e) => {
+                    self.frames.clear();
+                    self.rst_info = Some(e);
+                }
+            }
+            self.selected = 0;
+            self.read_locals(h, core_idx, dwarf);
+        }
+
+        fn read_locals(&mut self, h: &mut ProbeRsHandler, core_idx: usize, dwarf: &DwarfInfo) {
+            self.locals.clear();
+            self.deref_cache.clear();
+            let Some(frame) = self.frames.get(self.selected) else {
+                return;
+            };
+            let Some(function) = dwarf.function_at(frame.lookup_pc) else {
+                return;
+            };
+            let mut read = |address: u64, size: usize| -> Option<Vec<u8>> {
+                let mut buf = vec![0u8; size.min(m_config::WATCH_MAX_READ)];
+                h.read_memory(core_idx, address, 1, &mut buf)
+                    .ok()
+                    .map(|_| buf)
+            };
+            for var in function.locals.iter() {
+                let value =
+                    dwarf.read_local(function, var, &frame.registers, frame.lookup_pc, &mut read);
+                self.locals.push(LocalValue {
+                    name: var.name.clone(),
+                    type_id: var.type_id,
+                    value,
+                });
+            }
+        }
+
+        pub fn ui(
+            &mut self,
+            ui: &mut eframe::egui::Ui,
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            status: Option<CoreStatus>,
+            symbols: &ElfSymbols,
+            dwarf: &DwarfInfo,
+        ) {
+            let b_halted = status.is_some_and(|s| s.is_halted());
+            if b_halted && !self.b_was_halted {
+                self.refresh(h, core_idx, symbols, dwarf);
+            }
+            self.b_was_halted = b_halted;
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(b_halted, eframe::egui::Button::new("refresh"))
+                    .clicked()
+                {
+                    self.refresh(h, core_idx, symbols, dwarf);
+                }
+                if !b_halted {
+                    ui.label("halt the core to see the call stack");
+                }
+                if dwarf.is_empty() {
+                    ui.label("select an elf file with debug info for source locations");
+                }
+            });
+            if let Some(info) = self.rst_info.as_ref() {
+                ui.label(info);
+            }
+
+            let mut select = None;
+            eframe::egui::ScrollArea::vertical()
+                .id_salt("call_stack_frames")
+                .max_height(ui.available_height() / 2.0)
+                .show(ui, |ui| {
+                    eframe::egui::Grid::new("debug_call_stack")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (i, f) in self.frames.iter().enumerate() {
+                                let mut text = format!("#{} {}", i, f.function);
+                                if f.exception.is_some() {
+                                    text = format!("{} <exception>", text);
+                                }
+                                if ui.selectable_label(self.selected == i, text).clicked() {
+                                    select = Some(i);
+                                }
+                                ui.monospace(format!("{:#010x}", f.pc));
+                                match f.location.as_ref() {
+                                    Some((file, line)) => {
+                                        let name = Path::new(file)
+                                            .file_name()
+                                            .map_or(file.into(), |n| n.to_string_lossy());
+                                        ui.label(format!("{}:{}", name, line)).on_hover_text(file);
+                                    }
+                                    None => {
+                                        ui.label("-");
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            if let Some(i) = select {
+                self.selected = i;
+                self.read_locals(h, core_idx, dwarf);
+            }
+
+            ui.separator();
+            ui.label("locals");
+            let cache = &mut self.deref_cache;
+            let mut read = |address: u64, size: usize| -> Option<Vec<u8>> {
+                cache
+                    .entry((address, size))
+                    .or_insert_with(|| {
+                        let mut buf = vec![0u8; size.min(m_config::WATCH_MAX_READ)];
+                        h.read_memory(core_idx, address, 1, &mut buf)
+                            .ok()
+                            .map(|_| buf)
+                    })
+                    .clone()
+            };
+            let mut renderer = ValueRenderer {
+                dwarf,
+                symbols,
+                read: &mut read,
+            };
+            eframe::egui::ScrollArea::vertical()
+                .id_salt("call_stack_locals")
+                .auto_shrink(false)
+                .show(ui, |ui| {
+                    for (i, l) in self.locals.iter().enumerate() {
+                        match l.value.as_ref() {
+                            Ok(bytes) => renderer.ui(
+                                ui,
+                                &l.name,
+                                &format!("local_{}_{}_{}", self.selected, i, l.name),
+                                l.type_id,
+                                bytes,
+                            ),
+                            Err(e) => {
+                                ui.label(format!(
+                                    "{}: {} = <{}>",
+                                    l.name,
+                                    dwarf.type_name(l.type_id),
+                                    e
+                                ));
+                            }
+                        }
+                    }
+                });
+        }
+    }
+}
@@ -0,0 +1,231 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_chip_info_opts {
+    use crate::custom_targets::m_custom_targets::registry_generation;
+    use probe_rs::{
+        config::{self, MemoryRegion},
+        CoreType,
+    };
+    use std::ops::Range;
+
+    struct RegionRow {
+        kind: &'static str,
+        name: String,
+        range: Range<u64>,
+        cores: String,
+        /// Page size of the flash algorithm covering the region.
+        page_size: Option<u32>,
+    }
+
+    struct AlgorithmRow {
+        name: String,
+        b_default: bool,
+        range: Range<u64>,
+        page_size: u32,
+    }
+
+    struct ChipInfo {
+        family: String,
+        manufacturer: Option<String>,
+        cores: Vec<(String, CoreType)>,
+        regions: Vec<RegionRow>,
+        algorithms: Vec<AlgorithmRow>,
+        /// RTT scan ranges, None when every RAM region is scanned.
+        scan_regions: Option<Vec<Range<u64>>>,
+    }
+
+    fn size_label(size: u64) -> String {
+        if size >= 1024 * 1024 && size.is_multiple_of(1024 * 1024) {
+            format!("{} MiB", size / (1024 * 1024))
+        } else if size >= 1024 && size.is_multiple_of(1024) {
+            format!("{} KiB", size / 1024)
+        } else {
+            format!("{} B", size)
+        }
+    }
+
+    fn range_label(range: &Range<u64>) -> String {
+        format!("{:#010x}..{:#010x}", range.start, range.end)
+    }
+
+    /// Looks the chip up in the target registry.
+    fn lookup(chip: &str) -> Option<ChipInfo> {
+        let families = config::families();
+        let (family, variant) = families.iter().find_map(|f| {
+            f.variants()
+                .iter()
+                .find(|v| v.name.eq_ignore_ascii_case(chip))
+                .map(|v| (f, v))
+        })?;
+
+        let mut algorithms: Vec<AlgorithmRow> = variant
+            .flash_algorithms
+            .iter()
+            .filter_map(|name| family.get_algorithm(name))
+            .map(|a| AlgorithmRow {
+                name: a.name.clone(),
+                b_default: a.default,
+                range: a.flash_properties.address_range.clone(),
+                page_size: a.flash_properties.page_size,
+            })
+            .collect();
+        algorithms.sort_by_key(|a| (!a.b_default, a.range.start));
+
+        let regions = variant
+            .memory_map
+            .iter()
+            .map(|r| {
+                let (kind, name, range, cores) = match r {
+                    MemoryRegion::Nvm(r) if r.is_alias => {
+                        ("flash (alias)", &r.name, &r.range, &r.cores)
+                    }
+                    MemoryRegion::Nvm(r) => ("flash", &r.name, &r.range, &r.cores),
+                    MemoryRegion::Ram(r) => ("ram", &r.name, &r.range, &r.cores),
+                    MemoryRegion::Generic(r) => ("generic", &r.name, &r.range, &r.cores),
+                };
+                let page_size = matches!(r, MemoryRegion::Nvm(_))
+                    .then(|| {
+                        algorithms
+                            .iter()
+                            .find(|a| a.range.contains(&range.start))
+                            .map(|a| a.page_size)
+                    })
+                    .flatten();
+                RegionRow {
+                    kind,
+                    name: name.clone().unwrap_or_default(),
+                    range: range.clone(),
+                    cores: cores.join(", "),
+                    page_size,
+                }
+            })
+            .collect();
+
+        Some(ChipInfo {
+            family: family.name.clone(),
+            manufacturer: family
+                .manufacturer
+                .and_then(|m| m.get())
+                .map(|m| m.to_owned()),
+            cores: variant
+                .cores
+                .iter()
+                .map(|c| (c.name.clone(), c.core_type))
+                .collect(),
+            regions,
+            algorithms,
+            scan_regions: variant.rtt_scan_ranges.clone(),
+        })
+    }
+
+    #[derive(Default)]
+    pub struct ChipInfoView {
+        chip: String,
+        generation: usize,
+        info: Option<ChipInfo>,
+    }
+
+    impl ChipInfoView {
+        pub fn ui(&mut self, ui: &mut eframe::egui::Ui, chip: &str) {
+            eframe::egui::CollapsingHeader::new("chip info").show(ui, |ui| {
+                if chip.is_empty() {
+                    ui.label("no target selected");
+                    return;
+                }
+                let generation = registry_generation();
+                if self.chip != chip || self.generation != generation {
+                    self.chip = chip.to_owned();
+                    self.generation = generation;
+                    self.info = lookup(chip);
+                }
+                let Some(info) = self.info.as_ref() else {
+                    ui.label(format!("{} isn't in the target registry", chip));
+                    return;
+                };
+
+                match info.manufacturer.as_ref() {
+                    Some(m) => ui.label(format!("family: {} ({})", info.family, m)),
+                    None => ui.label(format!("family: {}", info.family)),
+                };
+
+                ui.label("cores");
+                eframe::egui::Grid::new("chip_info_cores")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (name, core_type) in info.cores.iter() {
+                            ui.label(name);
+                            ui.label(format!("{:?}", core_type));
+                            ui.end_row();
+                        }
+                    });
+
+                ui.label("memory map");
+                eframe::egui::Grid::new("chip_info_memory")
+                    .num_columns(6)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for h in ["kind", "name", "start", "size", "page size", "cores"] {
+                            ui.strong(h);
+                        }
+                        ui.end_row();
+                        for r in info.regions.iter() {
+                            ui.label(r.kind);
+                            ui.label(&r.name);
+                            ui.monospace(format!("{:#010x}", r.range.start));
+                            ui.label(size_label(r.range.end - r.range.start));
+                            ui.label(r.page_size.map_or("-".to_owned(), |p| size_label(p as u64)));
+                            ui.label(&r.cores);
+                            ui.end_row();
+                        }
+                    });
+
+                ui.label("flash algorithms");
+                if info.algorithms.is_empty() {
+                    ui.label("none, the chip can't be flashed");
+                }
+                eframe::egui::Grid::new("chip_info_algorithms")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for a in info.algorithms.iter() {
+                            if a.b_default {
+                                ui.label(format!("{} (default)", a.name));
+                            } else {
+                                ui.label(&a.name);
+                            }
+                            ui.monospace(range_label(&a.range));
+                            ui.label(format!("page {}", size_label(a.page_size as u64)));
+                            ui.end_row();
+                        }
+                    });
+
+                match info.scan_regions.as_ref() {
+                    Some(ranges) => {
+                        let ranges: Vec<String> = ranges.iter().map(range_label).collect();
+                        ui.label(format!("RTT scan regions: {}", ranges.join(", ")));
+                    }
+                    None => {
+                        ui.label("RTT scan regions: all RAM regions");
+                    }
+                }
+            });
+        }
+    }
+}
@@ -22,5 +22,46 @@ pub mod m_config {
     pub const WIN_HEIGHT: f32 = 720.0;
     pub const PAGE_1_LABEL: &str = "Flash";
     pub const PAGE_2_LABEL: &str = "RTT";
+    pub const PAGE_3_LABEL: &str = "Debug";
+    pub const PAGE_4_LABEL: &str = "Trace";
     pub const RTT_SYMBOL: &str = "_SEGGER_RTT";
+    pub const RTT_CONTROL_BLOCK_ID: &str = "SEGGER RTT";
+    pub const RTT_REATTACH_CHECK_INTERVAL_MS: u64 = 500;
+    pub const RTT_REATTACH_MARKER: &str = "--- target reset, re-attached ---";
+    pub const RTT_POLL_INTERVAL_MS: u64 = 10;
+    pub const RTT_READ_BUF_SIZE: usize = 1024;
+    pub const RTT_MAX_READS_PER_POLL: usize = 64;
+    pub const RTT_LOG_HISTORY_LEN: usize = 10000;
+    pub const RTT_TRIGGER_COOLDOWN_MS: u64 = 1000;
+    pub const RTT_TRIGGER_HISTORY_LEN: usize = 200;
+    pub const RTT_TRIGGER_ALERTS_SHOWN: usize = 5;
+    pub const RTT_TRIGGER_LINE_ENV: &str = "E_PROBE_RS_TRIGGER_LINE";
+    pub const CORE_HALT_TIMEOUT_MS: u64 = 100;
+    pub const CORE_STATUS_REFRESH_MS: u64 = 250;
+    pub const MEMORY_VIEW_LENGTH: usize = 256;
+    pub const MEMORY_VIEW_REFRESH_MS: u64 = 500;
+    pub const MEMORY_VIEW_ALIGN: u64 = 4;
+    pub const WATCH_VARIABLES_SHOWN: usize = 200;
+    pub const WATCH_REFRESH_MS: u64 = 500;
+    pub const WATCH_ARRAY_ELEMENTS_SHOWN: usize = 100;
+    pub const WATCH_MAX_READ: usize = 4096;
+    pub const CALL_STACK_MAX_FRAMES: usize = 64;
+    pub const DISASM_BYTES_BEFORE: u64 = 32;
+    pub const DISASM_BYTES_AFTER: usize = 160;
+    pub const GDB_DEFAULT_PORT: u16 = 1337;
+    pub const GDB_POLL_MS: u64 = 10;
+    pub const GDB_MAX_READ: usize = 0x1000;
+    pub const SVD_REFRESH_MS: u64 = 500;
+    pub const SEMIHOSTING_LOG_HISTORY_LEN: usize = 10000;
+    pub const SWO_DEFAULT_BAUD: u32 = 1_000_000;
+    pub const SWO_DEFAULT_TRACE_CLOCK_HZ: u32 = 64_000_000;
+    pub const SWO_POLL_INTERVAL_MS: u64 = 10;
+    pub const SWO_LOG_HISTORY_LEN: usize = 10000;
+    pub const SWO_PROFILE_ROWS_SHOWN: usize = 50;
+    pub const PROFILER_DEFAULT_DURATION_S: u64 = 5;
+    pub const PROFILER_DEFAULT_INTERVAL_MS: u64 = 1;
+    pub const PROFILER_FRAME_BUDGET_MS: u64 = 20;
+    pub const PROFILER_ROWS_SHOWN: usize = 100;
+    pub const PROFILER_FLAME_ROW_HEIGHT: f32 = 18.0;
+    pub const CUSTOM_TARGETS_FILE: &str = "custom_targets.txt";
 }
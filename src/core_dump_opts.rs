@@ -0,0 +1,161 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_core_dump_opts {
+    use crate::debug_opts::m_debug_opts::parse_number;
+    use crate::probe_rs_invoke::probe_rs_integration::ProbeRsHandler;
+    use egui_file::FileDialog;
+    use std::{ops::Range, path::PathBuf};
+
+    #[derive(Default)]
+    struct DumpRegion {
+        start: String,
+        size: String,
+    }
+
+    #[derive(Default)]
+    pub struct CoreDumpView {
+        regions: Vec<DumpRegion>,
+        save_dialog: Option<FileDialog>,
+        open_dialog: Option<FileDialog>,
+        dump_file: Option<PathBuf>,
+        rst_info: Option<String>,
+    }
+
+    impl CoreDumpView {
+        fn ranges(&self) -> Result<Vec<Range<u64>>, String> {
+            let mut ranges = Vec::new();
+            for (i, r) in self.regions.iter().enumerate() {
+                let start = parse_number(&r.start)
+                    .and_then(|v| u64::try_from(v).ok())
+                    .ok_or(format!("invalid start of region {}", i))?;
+                let size = parse_number(&r.size)
+                    .and_then(|v| u64::try_from(v).ok())
+                    .filter(|v| *v > 0)
+                    .ok_or(format!("invalid size of region {}", i))?;
+                let end = start
+                    .checked_add(size)
+                    .ok_or(format!("region {} overflows the address space", i))?;
+                ranges.push(start..end);
+            }
+            Ok(ranges)
+        }
+
+        /// The core dump being inspected, if any.
+        pub fn opened_file(&self) -> Option<&PathBuf> {
+            self.dump_file.as_ref()
+        }
+
+        pub fn ui(
+            &mut self,
+            ctx: &eframe::egui::Context,
+            ui: &mut eframe::egui::Ui,
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+        ) {
+            if h.core_dump.is_none() {
+                self.dump_file = None;
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button("add RAM regions").clicked() {
+                    for r in h.ram_regions() {
+                        self.regions.push(DumpRegion {
+                            start: format!("{:#010x}", r.start),
+                            size: format!("{:#x}", r.end - r.start),
+                        });
+                    }
+                }
+                if ui.button("add region").clicked() {
+                    self.regions.push(DumpRegion::default());
+                }
+                if ui.button("save core dump").clicked() {
+                    let mut dialog = FileDialog::save_file(None);
+                    dialog.open();
+                    self.save_dialog = Some(dialog);
+                }
+                ui.separator();
+                if ui.button("open core dump").clicked() {
+                    let mut dialog = FileDialog::open_file(self.dump_file.clone());
+                    dialog.open();
+                    self.open_dialog = Some(dialog);
+                }
+                if self.dump_file.is_some() && ui.button("close core dump").clicked() {
+                    h.close_core_dump();
+                    self.dump_file = None;
+                }
+            });
+
+            let mut remove = None;
+            eframe::egui::Grid::new("debug_core_dump_regions")
+                .num_columns(3)
+                .show(ui, |ui| {
+                    for (i, r) in self.regions.iter_mut().enumerate() {
+                        ui.add(
+                            eframe::egui::TextEdit::singleline(&mut r.start)
+                                .hint_text("start")
+                                .desired_width(120.0),
+                        );
+                        ui.add(
+                            eframe::egui::TextEdit::singleline(&mut r.size)
+                                .hint_text("size")
+                                .desired_width(120.0),
+                        );
+                        if ui.button("remove").clicked() {
+                            remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(i) = remove {
+                self.regions.remove(i);
+            }
+
+            if let Some(dialog) = &mut self.save_dialog {
+                if dialog.show(ctx).selected() {
+                    if let Some(file) = dialog.path().map(|p| p.to_path_buf()) {
+                        let rst = self.ranges().and_then(|ranges| {
+                            h.save_core_dump(core_idx, ranges, &file)
+                                .map_err(|e| format!("{}", e))
+                        });
+                        self.rst_info = Some(match rst {
+                            Ok(_) => format!("core dump saved to {:?}", file),
+                            Err(e) => e,
+                        });
+                    }
+                }
+            }
+            if let Some(dialog) = &mut self.open_dialog {
+                if dialog.show(ctx).selected() {
+                    if let Some(file) = dialog.path().map(|p| p.to_path_buf()) {
+                        match h.open_core_dump(&file) {
+                            Ok(_) => {
+                                self.dump_file = Some(file);
+                                self.rst_info.take();
+                            }
+                            Err(e) => self.rst_info = Some(format!("{}", e)),
+                        }
+                    }
+                }
+            }
+            if let Some(info) = self.rst_info.as_ref() {
+                ui.label(info);
+            }
+        }
+    }
+}
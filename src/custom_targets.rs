@@ -0,0 +1,204 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_custom_targets {
+    use crate::configuration::m_config;
+    use egui_file::FileDialog;
+    use probe_rs::config;
+    use std::{
+        error::Error,
+        fs::{self, File},
+        path::{Path, PathBuf},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Mutex,
+        },
+    };
+
+    /// Bumped whenever the registry changes, so chip lists know to rebuild.
+    static REGISTRY_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+    /// Families added from target description files, lowercase.
+    static CUSTOM_FAMILIES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    pub fn registry_generation() -> usize {
+        REGISTRY_GENERATION.load(Ordering::Relaxed)
+    }
+
+    /// Whether the family was loaded from a target description file.
+    pub fn is_custom_family(name: &str) -> bool {
+        let name = name.to_lowercase();
+        CUSTOM_FAMILIES
+            .lock()
+            .map(|f| f.contains(&name))
+            .unwrap_or(false)
+    }
+
+    /// File holding the remembered target description paths, one per line.
+    fn list_file() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| {
+            d.join(m_config::APP_NAME)
+                .join(m_config::CUSTOM_TARGETS_FILE)
+        })
+    }
+
+    fn saved_files() -> Vec<PathBuf> {
+        list_file()
+            .and_then(|f| fs::read_to_string(f).ok())
+            .map(|s| {
+                s.lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty())
+                    .map(PathBuf::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn save_files(files: &[PathBuf]) -> Result<(), Box<dyn Error>> {
+        let f = list_file().ok_or("no configuration directory on this system")?;
+        if let Some(dir) = f.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut s = String::new();
+        for p in files {
+            s.push_str(&p.to_string_lossy());
+            s.push('\n');
+        }
+        fs::write(f, s)?;
+        Ok(())
+    }
+
+    /// Adds the target description to the registry, returns the family name.
+    fn register(path: &Path) -> Result<String, Box<dyn Error>> {
+        let family = config::add_target_from_yaml(File::open(path)?)?;
+        if let Ok(mut families) = CUSTOM_FAMILIES.lock() {
+            let name = family.to_lowercase();
+            if !families.contains(&name) {
+                families.push(name);
+            }
+        }
+        REGISTRY_GENERATION.fetch_add(1, Ordering::Relaxed);
+        Ok(family)
+    }
+
+    pub struct CustomTarget {
+        pub path: PathBuf,
+        /// Family the file describes, or why it couldn't be loaded.
+        pub family: Result<String, String>,
+    }
+
+    /// Registers the remembered target descriptions, call before listing chips.
+    pub fn load_saved() -> Vec<CustomTarget> {
+        saved_files()
+            .into_iter()
+            .map(|path| {
+                let family = register(&path).map_err(|e| format!("{}", e));
+                CustomTarget { path, family }
+            })
+            .collect()
+    }
+
+    #[derive(Default)]
+    pub struct CustomTargetsView {
+        targets: Vec<CustomTarget>,
+        file_dialog: Option<FileDialog>,
+        rst_info: Option<String>,
+    }
+
+    impl CustomTargetsView {
+        pub fn new(targets: Vec<CustomTarget>) -> Self {
+            Self {
+                targets,
+                ..Default::default()
+            }
+        }
+
+        fn remember(&self) -> Result<(), Box<dyn Error>> {
+            let files: Vec<PathBuf> = self.targets.iter().map(|t| t.path.clone()).collect();
+            save_files(&files)
+        }
+
+        fn add(&mut self, path: PathBuf) {
+            match register(&path) {
+                Ok(family) => {
+                    self.rst_info = Some(format!("family {} loaded", family));
+                    match self.targets.iter_mut().find(|t| t.path == path) {
+                        Some(t) => t.family = Ok(family),
+                        None => self.targets.push(CustomTarget {
+                            path,
+                            family: Ok(family),
+                        }),
+                    }
+                    if let Err(e) = self.remember() {
+                        self.rst_info = Some(format!("{}", e));
+                    }
+                }
+                Err(e) => self.rst_info = Some(format!("{}", e)),
+            }
+        }
+
+        pub fn ui(&mut self, ctx: &eframe::egui::Context, ui: &mut eframe::egui::Ui) {
+            ui.horizontal(|ui| {
+                if ui.button("load target yaml").clicked() {
+                    let last = self.targets.last().map(|t| t.path.clone());
+                    let mut dialog = FileDialog::open_file(last);
+                    dialog.open();
+                    self.file_dialog = Some(dialog);
+                }
+                if let Some(info) = self.rst_info.as_ref() {
+                    ui.label(info);
+                }
+            });
+            if let Some(dialog) = &mut self.file_dialog {
+                if dialog.show(ctx).selected() {
+                    if let Some(file) = dialog.path().map(|p| p.to_path_buf()) {
+                        self.add(file);
+                    }
+                }
+            }
+
+            let mut forget = None;
+            eframe::egui::Grid::new("flash_custom_targets")
+                .num_columns(3)
+                .show(ui, |ui| {
+                    for (i, t) in self.targets.iter().enumerate() {
+                        ui.label(t.path.to_string_lossy());
+                        match &t.family {
+                            Ok(family) => ui.label(family),
+                            Err(e) => ui.colored_label(ui.visuals().error_fg_color, e),
+                        };
+                        if ui.button("forget").clicked() {
+                            forget = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(i) = forget {
+                let t = self.targets.remove(i);
+                self.rst_info = Some(match self.remember() {
+                    Ok(_) => format!(
+                        "{} forgotten, its chips stay listed until restart",
+                        t.path.to_string_lossy()
+                    ),
+                    Err(e) => format!("{}", e),
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,546 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_debug_opts {
+    use crate::breakpoint_opts::m_breakpoint_opts::BreakpointView;
+    use crate::call_stack_opts::m_call_stack_opts::CallStackView;
+    use crate::chip_info_opts::m_chip_info_opts::ChipInfoView;
+    use crate::configuration::m_config;
+    use crate::core_dump_opts::m_core_dump_opts::CoreDumpView;
+    use crate::disasm_opts::m_disasm_opts::DisasmView;
+    use crate::dwarf_info::m_dwarf_info::DwarfInfo;
+    use crate::elf_symbols::m_elf_symbols::ElfSymbols;
+    use crate::fault_opts::m_fault_opts::FaultView;
+    use crate::memory_opts::m_memory_opts::MemoryView;
+    use crate::peripheral_opts::m_peripheral_opts::PeripheralView;
+    use crate::probe_opts::m_probe_opts::{chip_detection_ui, probe_config_ui, unlock_ui};
+    use crate::probe_rs_invoke::probe_rs_integration::ProbeRsHandler;
+    use crate::profiler_opts::m_profiler_opts::ProfilerView;
+    use crate::register_opts::m_register_opts::RegisterView;
+    use crate::semihosting_opts::m_semihosting_opts::SemihostingView;
+    use crate::watch_opts::m_watch_opts::WatchView;
+    use crate::watchpoint_opts::m_watchpoint_opts::WatchpointView;
+    use egui_file::FileDialog;
+    use probe_rs::{CoreStatus, HaltReason};
+    use std::{
+        borrow::{Borrow, BorrowMut},
+        path::PathBuf,
+        time::{Duration, Instant},
+    };
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    enum DebugPanel {
+        #[default]
+        Registers,
+        Memory,
+        Breakpoints,
+        Watchpoints,
+        Watch,
+        CallStack,
+        Disassembly,
+        Fault,
+        Peripherals,
+        Semihosting,
+        Profiler,
+        CoreDump,
+    }
+
+    /// Parses a number typed by the user, accepting `0x`/`0b`/`0o` prefixes and `_`
+    /// separators.
+    pub fn parse_number(text: &str) -> Option<u128> {
+        let t = text.trim().replace('_', "");
+        let lower = t.to_ascii_lowercase();
+        if let Some(hex) = lower.strip_prefix("0x") {
+            u128::from_str_radix(hex, 16).ok()
+        } else if let Some(bin) = lower.strip_prefix("0b") {
+            u128::from_str_radix(bin, 2).ok()
+        } else if let Some(oct) = lower.strip_prefix("0o") {
+            u128::from_str_radix(oct, 8).ok()
+        } else {
+            lower.parse::<u128>().ok()
+        }
+    }
+
+    pub fn core_status_text(status: &CoreStatus) -> String {
+        match status {
+            CoreStatus::Running => "running".to_owned(),
+            CoreStatus::Halted(reason) => {
+                let r = match reason {
+                    HaltReason::Multiple => "multiple".to_owned(),
+                    HaltReason::Breakpoint(cause) => format!("breakpoint, {:?}", cause),
+                    HaltReason::Exception => "exception".to_owned(),
+                    HaltReason::Watchpoint => "watchpoint".to_owned(),
+                    HaltReason::Step => "step".to_owned(),
+                    HaltReason::Request => "request".to_owned(),
+                    HaltReason::External => "external".to_owned(),
+                    HaltReason::Unknown => "unknown".to_owned(),
+                };
+                format!("halted ({})", r)
+            }
+            CoreStatus::LockedUp => "locked up".to_owned(),
+            CoreStatus::Sleeping => "sleeping".to_owned(),
+            CoreStatus::Unknown => "unknown".to_owned(),
+        }
+    }
+
+    #[derive(Default)]
+    pub struct CoreDebug {
+        probe_selected_idx: usize,
+        probe_rs_handler: Option<ProbeRsHandler>,
+        target_chip_name: String,
+        filter_s: String,
+        cores_status: Vec<Option<CoreStatus>>,
+        last_status_refresh: Option<Instant>,
+        op_rst_info: Option<String>,
+        b_unlock_confirmed: bool,
+        chip_info: ChipInfoView,
+        cur_target_core_idx: usize,
+        panel: DebugPanel,
+        register_view: RegisterView,
+        memory_view: MemoryView,
+        breakpoint_view: BreakpointView,
+        watchpoint_view: WatchpointView,
+        watch_view: WatchView,
+        call_stack_view: CallStackView,
+        disasm_view: DisasmView,
+        fault_view: FaultView,
+        peripheral_view: PeripheralView,
+        semihosting_view: SemihostingView,
+        profiler_view: ProfilerView,
+        core_dump_view: CoreDumpView,
+        file_dialog: Option<FileDialog>,
+        selected_file: Option<PathBuf>,
+        elf_symbols: ElfSymbols,
+        dwarf_info: DwarfInfo,
+        elf_rst_info: Option<String>,
+    }
+
+    impl CoreDebug {
+        fn refresh_cores_status(&mut self) {
+            self.last_status_refresh = Some(Instant::now());
+            self.cores_status.clear();
+            if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                for c in 0..h.target_cores_num {
+                    self.cores_status.push(h.core_status(c).unwrap_or_default());
+                }
+            }
+        }
+
+        fn core_control_ui(&mut self, ui: &mut eframe::egui::Ui) {
+            let timeout = Duration::from_millis(m_config::CORE_HALT_TIMEOUT_MS);
+            if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                let mut rst = None;
+                eframe::egui::Grid::new("debug_core_control")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for c in 0..h.target_cores_num {
+                            ui.label(format!("core {}", c));
+                            let status = self.cores_status.get(c).copied().flatten();
+                            ui.label(status.as_ref().map_or("-".to_owned(), core_status_text));
+                            if ui.button("halt").clicked() {
+                                rst = Some(h.halt_core(c, timeout));
+                            }
+                            if ui.button("run").clicked() {
+                                rst = Some(h.run_core(c));
+                            }
+                            if ui.button("step").clicked() {
+                                rst = Some(h.step_core(c));
+                            }
+                            if ui.button("reset").clicked() {
+                                rst = Some(h.reset_core(c));
+                            }
+                            if ui.button("reset and halt").clicked() {
+                                rst = Some(h.reset_and_halt_core(c, timeout));
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(r) = rst {
+                    match r {
+                        Ok(_) => {
+                            self.op_rst_info.take();
+                        }
+                        Err(e) => {
+                            self.op_rst_info = Some(format!("{}", e));
+                        }
+                    }
+                    // Show the effect of the operation right away.
+                    self.last_status_refresh = None;
+                }
+            }
+        }
+
+        fn load_elf(&mut self) {
+            if let Some(f) = self.selected_file.as_ref() {
+                match ElfSymbols::load(f) {
+                    Ok(symbols) => {
+                        self.elf_symbols = symbols;
+                        self.elf_rst_info.take();
+                    }
+                    Err(e) => {
+                        self.elf_symbols = ElfSymbols::default();
+                        self.elf_rst_info = Some(format!("{}", e));
+                    }
+                }
+                match DwarfInfo::load(f) {
+                    Ok(dwarf) => {
+                        if dwarf.is_empty() {
+                            self.elf_rst_info
+                                .get_or_insert("no DWARF line info".to_owned());
+                        }
+                        self.dwarf_info = dwarf;
+                    }
+                    Err(e) => {
+                        self.dwarf_info = DwarfInfo::default();
+                        self.elf_rst_info.get_or_insert(format!("{}", e));
+                    }
+                }
+            }
+        }
+
+        /// Keeps the core status fresh while any page is shown. Returns true when a
+        /// watchpoint halted a core.
+        pub fn poll(&mut self, ctx: &eframe::egui::Context) -> bool {
+            if self
+                .probe_rs_handler
+                .as_ref()
+                .is_none_or(|h| h.session.is_none() && h.core_dump.is_none())
+            {
+                return false;
+            }
+            let refresh_interval = Duration::from_millis(m_config::CORE_STATUS_REFRESH_MS);
+            if self
+                .last_status_refresh
+                .is_none_or(|t| t.elapsed() >= refresh_interval)
+            {
+                self.refresh_cores_status();
+            }
+            let mut b_watchpoint_hit = false;
+            if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                self.breakpoint_view.poll(h, &self.cores_status);
+                b_watchpoint_hit = self.watchpoint_view.poll(
+                    h,
+                    &self.cores_status,
+                    &self.elf_symbols,
+                    &self.dwarf_info,
+                );
+                if self.semihosting_view.poll(h, &self.cores_status) {
+                    // The core was resumed, look for its next request right away.
+                    self.last_status_refresh = None;
+                    ctx.request_repaint();
+                }
+                if self
+                    .profiler_view
+                    .poll(h, &self.elf_symbols, &self.dwarf_info)
+                {
+                    ctx.request_repaint();
+                }
+            }
+            ctx.request_repaint_after(refresh_interval);
+            b_watchpoint_hit
+        }
+
+        pub fn ui(&mut self, ctx: &eframe::egui::Context, ui: &mut eframe::egui::Ui) {
+            if self.probe_rs_handler.borrow_mut().is_none() {
+                self.probe_rs_handler = Some(ProbeRsHandler::default());
+            }
+            if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                if h.probes_list.is_empty() {
+                    h.get_probes_list();
+                }
+            }
+
+            ui.horizontal(|ui| {
+                eframe::egui::ComboBox::from_label("probe")
+                    .selected_text(format!("{}", self.probe_selected_idx))
+                    .show_ui(ui, |ui| {
+                        if let Some(h) = self.probe_rs_handler.borrow() {
+                            for (i, p) in h.probes_list.iter().enumerate() {
+                                ui.selectable_value(
+                                    &mut self.probe_selected_idx,
+                                    i,
+                                    format!(
+                                        "{} (pid: {} vid: {})",
+                                        p.identifier.as_str(),
+                                        p.product_id,
+                                        p.vendor_id
+                                    ),
+                                );
+                            }
+                        }
+                    });
+                if ui.button("refresh").clicked() {
+                    if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                        h.get_probes_list();
+                    }
+                }
+                if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                    probe_config_ui(ui, h);
+                }
+            });
+            if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                h.get_availabe_chips();
+            }
+
+            ui.horizontal(|ui| {
+                if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                    eframe::egui::ComboBox::from_label("target")
+                        .selected_text(h.chip_label(&self.target_chip_name))
+                        .show_ui(ui, |ui| {
+                            for t in h.chips_list.iter() {
+                                if !self.filter_s.is_empty() {
+                                    if t.contains(&self.filter_s) {
+                                        ui.selectable_value(
+                                            &mut self.target_chip_name,
+                                            t.to_string(),
+                                            h.chip_label(t),
+                                        );
+                                    }
+                                } else {
+                                    ui.selectable_value(
+                                        &mut self.target_chip_name,
+                                        t.to_string(),
+                                        h.chip_label(t),
+                                    );
+                                }
+                            }
+                        });
+
+                    ui.add(
+                        eframe::egui::TextEdit::singleline(&mut self.filter_s)
+                            .hint_text("chips filter")
+                            .desired_width(100.0),
+                    );
+
+                    if ui.button("attach").clicked() {
+                        match h.attach_target(self.probe_selected_idx, &self.target_chip_name) {
+                            Ok(_) => {
+                                self.op_rst_info.take();
+                            }
+                            Err(e) => {
+                                self.op_rst_info = Some(format!("{:#?}", e));
+                            }
+                        }
+                        h.get_core_num();
+                        self.last_status_refresh = None;
+                    }
+                    if ui.button("attach under reset").clicked() {
+                        match h.attach_target_under_reset(
+                            self.probe_selected_idx,
+                            &self.target_chip_name,
+                        ) {
+                            Ok(_) => {
+                                self.op_rst_info.take();
+                            }
+                            Err(e) => {
+                                self.op_rst_info = Some(format!("{:#?}", e));
+                            }
+                        }
+                        h.get_core_num();
+                        self.last_status_refresh = None;
+                    }
+                    if ui.button("reset all").clicked() && h.reset_all_cores().is_ok() {
+                        self.target_chip_name = "".to_owned();
+                        self.cores_status.clear();
+                        self.op_rst_info.take();
+                        self.cur_target_core_idx = 0;
+                        self.register_view = RegisterView::default();
+                        self.memory_view = MemoryView::default();
+                        self.breakpoint_view = BreakpointView::default();
+                        self.watchpoint_view = WatchpointView::default();
+                        self.watch_view = WatchView::default();
+                        self.call_stack_view = CallStackView::default();
+                        self.disasm_view = DisasmView::default();
+                        self.fault_view = FaultView::default();
+                        self.peripheral_view = PeripheralView::default();
+                        self.semihosting_view = SemihostingView::default();
+                        self.profiler_view = ProfilerView::default();
+                        self.core_dump_view = CoreDumpView::default();
+                        self.probe_rs_handler = None;
+                    }
+                }
+            });
+            if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                self.chip_info.ui(ui, &self.target_chip_name);
+                chip_detection_ui(ui, h, self.probe_selected_idx, &mut self.target_chip_name);
+                if unlock_ui(
+                    ui,
+                    h,
+                    &mut self.b_unlock_confirmed,
+                    self.probe_selected_idx,
+                    &self.target_chip_name,
+                ) {
+                    self.op_rst_info.take();
+                    self.last_status_refresh = None;
+                }
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button("Select elf file").clicked() {
+                    let mut dialog = FileDialog::open_file(self.selected_file.clone());
+                    dialog.open();
+                    self.file_dialog = Some(dialog);
+                }
+                ui.label(format!("Selected elf file: {:?}", self.selected_file));
+                if let Some(info) = self.elf_rst_info.as_ref() {
+                    ui.label(info);
+                }
+            });
+            let mut b_elf_selected = false;
+            if let Some(dialog) = &mut self.file_dialog {
+                if dialog.show(ctx).selected() {
+                    if let Some(file) = dialog.path() {
+                        self.selected_file = Some(file.to_path_buf());
+                        b_elf_selected = true;
+                    }
+                }
+            }
+            if b_elf_selected {
+                self.load_elf();
+            }
+
+            ui.separator();
+
+            if let Some(f) = self.core_dump_view.opened_file() {
+                ui.label(format!("Inspecting core dump: {:?}", f));
+            }
+            self.core_control_ui(ui);
+            ui.label(self.op_rst_info.clone().unwrap_or_default());
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if let Some(h) = self.probe_rs_handler.borrow() {
+                    eframe::egui::ComboBox::from_label("core")
+                        .selected_text(format!("{}", self.cur_target_core_idx))
+                        .show_ui(ui, |ui| {
+                            for c in 0..h.target_cores_num {
+                                ui.selectable_value(
+                                    &mut self.cur_target_core_idx,
+                                    c,
+                                    format!("{}", c),
+                                );
+                            }
+                        });
+                }
+                ui.separator();
+                ui.selectable_value(&mut self.panel, DebugPanel::Registers, "Registers");
+                ui.selectable_value(&mut self.panel, DebugPanel::Memory, "Memory");
+                ui.selectable_value(&mut self.panel, DebugPanel::Breakpoints, "Breakpoints");
+                ui.selectable_value(&mut self.panel, DebugPanel::Watchpoints, "Watchpoints");
+                ui.selectable_value(&mut self.panel, DebugPanel::Watch, "Watch");
+                ui.selectable_value(&mut self.panel, DebugPanel::CallStack, "Call stack");
+                ui.selectable_value(&mut self.panel, DebugPanel::Disassembly, "Disassembly");
+                ui.selectable_value(&mut self.panel, DebugPanel::Fault, "Fault");
+                ui.selectable_value(&mut self.panel, DebugPanel::Peripherals, "Peripherals");
+                ui.selectable_value(&mut self.panel, DebugPanel::Semihosting, "Semihosting");
+                ui.selectable_value(&mut self.panel, DebugPanel::Profiler, "Profiler");
+                ui.selectable_value(&mut self.panel, DebugPanel::CoreDump, "Core dump");
+            });
+            ui.separator();
+
+            let status = self
+                .cores_status
+                .get(self.cur_target_core_idx)
+                .copied()
+                .flatten();
+            if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                match self.panel {
+                    DebugPanel::Registers => {
+                        self.register_view
+                            .ui(ui, h, self.cur_target_core_idx, status);
+                    }
+                    DebugPanel::Memory => {
+                        self.memory_view
+                            .ui(ui, h, self.cur_target_core_idx, &self.elf_symbols);
+                    }
+                    DebugPanel::Breakpoints => {
+                        self.breakpoint_view.ui(
+                            ui,
+                            h,
+                            self.cur_target_core_idx,
+                            &self.elf_symbols,
+                            &self.dwarf_info,
+                        );
+                    }
+                    DebugPanel::Watchpoints => {
+                        self.watchpoint_view.ui(
+                            ui,
+                            h,
+                            self.cur_target_core_idx,
+                            &self.elf_symbols,
+                            &self.dwarf_info,
+                        );
+                    }
+                    DebugPanel::Watch => {
+                        self.watch_view.ui(
+                            ui,
+                            h,
+                            self.cur_target_core_idx,
+                            &self.elf_symbols,
+                            &self.dwarf_info,
+                        );
+                    }
+                    DebugPanel::CallStack => {
+                        self.call_stack_view.ui(
+                            ui,
+                            h,
+                            self.cur_target_core_idx,
+                            status,
+                            &self.elf_symbols,
+                            &self.dwarf_info,
+                        );
+                    }
+                    DebugPanel::Disassembly => {
+                        self.disasm_view.ui(
+                            ui,
+                            h,
+                            self.cur_target_core_idx,
+                            status,
+                            &self.elf_symbols,
+                            &self.dwarf_info,
+                            &mut self.breakpoint_view,
+                        );
+                    }
+                    DebugPanel::Fault => {
+                        self.fault_view.ui(
+                            ui,
+                            h,
+                            self.cur_target_core_idx,
+                            &self.elf_symbols,
+                            &self.dwarf_info,
+                        );
+                    }
+                    DebugPanel::Peripherals => {
+                        self.peripheral_view
+                            .ui(ui, h, self.cur_target_core_idx, status);
+                    }
+                    DebugPanel::Semihosting => {
+                        self.semihosting_view.ui(ui, self.cur_target_core_idx);
+                    }
+                    DebugPanel::Profiler => {
+                        self.profiler_view.ui(ui, h, self.cur_target_core_idx);
+                    }
+                    DebugPanel::CoreDump => {
+                        self.core_dump_view.ui(ctx, ui, h, self.cur_target_core_idx);
+                    }
+                }
+            }
+            self.watchpoint_view.alert_ui(ctx);
+        }
+    }
+}
@@ -0,0 +1,358 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_disasm_opts {
+    use crate::breakpoint_opts::m_breakpoint_opts::{
+        describe_address, resolve_location, BreakpointView,
+    };
+    use crate::configuration::m_config;
+    use crate::dwarf_info::m_dwarf_info::DwarfInfo;
+    use crate::elf_symbols::m_elf_symbols::ElfSymbols;
+    use crate::probe_rs_invoke::probe_rs_integration::ProbeRsHandler;
+    use crate::riscv_disasm::m_riscv_disasm;
+    use capstone::prelude::*;
+    use probe_rs::{CoreStatus, CoreType};
+    use std::{collections::HashMap, fs, path::Path};
+
+    pub struct Instruction {
+        pub address: u64,
+        pub bytes: Vec<u8>,
+        pub text: String,
+    }
+
+    /// Disassembles `code` loaded at `address` for the given core. Bytes that don't
+    /// decode are shown as data and skipped.
+    pub fn disassemble(
+        core_type: CoreType,
+        b_rv64: bool,
+        code: &[u8],
+        address: u64,
+    ) -> Result<Vec<Instruction>, String> {
+        let mut insns = Vec::new();
+        if core_type == CoreType::Riscv {
+            let mut off = 0;
+            while let Some((len, text)) =
+                m_riscv_disasm::decode(&code[off..], address + off as u64, b_rv64)
+            {
+                insns.push(Instruction {
+                    address: address + off as u64,
+                    bytes: code[off..off + len].to_vec(),
+                    text,
+                });
+                off += len;
+            }
+            return Ok(insns);
+        }
+
+        let (cs, step) = match core_type {
+            CoreType::Armv6m | CoreType::Armv7m | CoreType::Armv7em => (
+                Capstone::new()
+                    .arm()
+                    .mode(arch::arm::ArchMode::Thumb)
+                    .extra_mode([arch::arm::ArchExtraMode::MClass].into_iter())
+                    .build(),
+                2,
+            ),
+            CoreType::Armv8m => (
+                Capstone::new()
+                    .arm()
+                    .mode(arch::arm::ArchMode::Thumb)
+                    .extra_mode(
+                        [
+                            arch::arm::ArchExtraMode::MClass,
+                            arch::arm::ArchExtraMode::V8,
+                        ]
+                        .into_iter(),
+                    )
+                    .build(),
+                2,
+            ),
+            CoreType::Armv7a => (
+                Capstone::new().arm().mode(arch::arm::ArchMode::Arm).build(),
+                4,
+            ),
+            CoreType::Armv8a => (
+                Capstone::new()
+                    .arm64()
+                    .mode(arch::arm64::ArchMode::Arm)
+                    .build(),
+                4,
+            ),
+            _ => return Err(format!("no disassembler for {:?} cores", core_type)),
+        };
+        let cs = cs.map_err(|e| format!("{}", e))?;
+        let mut off = 0;
+        while off + step <= code.len() {
+            let decoded = cs
+                .disasm_all(&code[off..], address + off as u64)
+                .map_err(|e| format!("{}", e))?;
+            for i in decoded.iter() {
+                insns.push(Instruction {
+                    address: i.address(),
+                    bytes: i.bytes().to_vec(),
+                    text: format!(
+                        "{} {}",
+                        i.mnemonic().unwrap_or(""),
+                        i.op_str().unwrap_or("")
+                    ),
+                });
+                off += i.bytes().len();
+            }
+            // capstone stops at the first invalid instruction.
+            if off + step <= code.len() {
+                insns.push(Instruction {
+                    address: address + off as u64,
+                    bytes: code[off..off + step].to_vec(),
+                    text: "<invalid>".to_owned(),
+                });
+                off += step;
+            }
+        }
+        Ok(insns)
+    }
+
+    enum DisasmLine {
+        Label(String),
+        Source {
+            location: String,
+            text: Option<String>,
+        },
+        Instruction(Instruction),
+    }
+
+    #[derive(Default)]
+    pub struct DisasmView {
+        location: String,
+        /// Address to disassemble around, None to follow the PC.
+        anchor: Option<u64>,
+        lines: Vec<DisasmLine>,
+        pc: Option<u64>,
+        sources: HashMap<String, Option<Vec<String>>>,
+        b_was_halted: bool,
+        b_scroll_to_anchor: bool,
+        rst_info: Option<String>,
+    }
+
+    impl DisasmView {
+        fn source_line(&mut self, file: &str, line: u64) -> Option<String> {
+            self.sources
+                .entry(file.to_owned())
+                .or_insert_with(|| {
+                    fs::read_to_string(file)
+                        .ok()
+                        .map(|s| s.lines().map(|l| l.to_owned()).collect())
+                })
+                .as_ref()?
+                .get((line as usize).checked_sub(1)?)
+                .map(|l| l.trim_end().to_owned())
+        }
+
+        fn refresh(
+            &mut self,
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            symbols: &ElfSymbols,
+            dwarf: &DwarfInfo,
+        ) -> Result<(), String> {
+            self.lines.clear();
+            let core_type = h
+                .core_type(core_idx)
+                .map_err(|e| format!("{}", e))?
+                .ok_or("no core attached")?;
+            self.pc = h.read_pc(core_idx).ok().flatten();
+            let anchor = match self.anchor {
+                Some(a) => a,
+                None => self.pc.ok_or("can't read the program counter")?,
+            };
+
+            let before = anchor.min(m_config::DISASM_BYTES_BEFORE);
+            let start = anchor - before;
+            let mut code = vec![0u8; before as usize + m_config::DISASM_BYTES_AFTER];
+            h.read_memory(core_idx, start, 1, &mut code)
+                .map_err(|e| format!("{}", e))?;
+
+            // Instructions have variable length, find a start that lines up with the anchor.
+            let b_rv64 = dwarf.address_size == 8;
+            let mut insns = Vec::new();
+            for skip in (0..=before as usize).step_by(2) {
+                insns = disassemble(core_type, b_rv64, &code[skip..], start + skip as u64)?;
+                if insns.iter().any(|i| i.address == anchor) {
+                    break;
+                }
+            }
+
+            let mut last_location = None;
+            for i in insns {
+                if let Some((s, 0)) = symbols.symbol_at(i.address) {
+                    if s.b_func {
+                        self.lines.push(DisasmLine::Label(s.name.clone()));
+                    }
+                }
+                let location = dwarf.location_at(i.address);
+                if location.is_some() && location != last_location {
+                    if let Some((file, line)) = location {
+                        let name = Path::new(file)
+                            .file_name()
+                            .map_or(file.into(), |n| n.to_string_lossy());
+                        let text = self.source_line(file, line);
+                        self.lines.push(DisasmLine::Source {
+                            location: format!("{}:{}", name, line),
+                            text,
+                        });
+                    }
+                    last_location = location;
+                }
+                self.lines.push(DisasmLine::Instruction(i));
+            }
+            self.b_scroll_to_anchor = true;
+            Ok(())
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        pub fn ui(
+            &mut self,
+            ui: &mut eframe::egui::Ui,
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            status: Option<CoreStatus>,
+            symbols: &ElfSymbols,
+            dwarf: &DwarfInfo,
+            breakpoints: &mut BreakpointView,
+        ) {
+            let mut b_refresh = false;
+            let b_halted = status.is_some_and(|s| s.is_halted());
+            if b_halted && !self.b_was_halted {
+                b_refresh = true;
+            }
+            self.b_was_halted = b_halted;
+
+            ui.horizontal(|ui| {
+                let resp = ui.add(
+                    eframe::egui::TextEdit::singleline(&mut self.location)
+                        .hint_text("address or symbol")
+                        .desired_width(200.0),
+                );
+                let b_enter =
+                    resp.lost_focus() && ui.input(|i| i.key_pressed(eframe::egui::Key::Enter));
+                if ui.button("go").clicked() || b_enter {
+                    match resolve_location(&self.location, symbols, dwarf) {
+                        Ok(a) => {
+                            self.anchor = Some(a);
+                            b_refresh = true;
+                        }
+                        Err(e) => self.rst_info = Some(e),
+                    }
+                }
+                if ui
+                    .selectable_label(self.anchor.is_none(), "follow pc")
+                    .clicked()
+                {
+                    self.anchor = None;
+                    b_refresh = true;
+                }
+                if ui.button("refresh").clicked() {
+                    b_refresh = true;
+                }
+            });
+            if b_refresh {
+                match self.refresh(h, core_idx, symbols, dwarf) {
+                    Ok(_) => {
+                        self.rst_info.take();
+                    }
+                    Err(e) => self.rst_info = Some(e),
+                }
+            }
+            if let Some(info) = self.rst_info.as_ref() {
+                ui.label(info);
+            }
+
+            let anchor = self.anchor.or(self.pc);
+            let mut toggle = None;
+            eframe::egui::ScrollArea::vertical()
+                .auto_shrink(false)
+                .show(ui, |ui| {
+                    eframe::egui::Grid::new("debug_disasm")
+                        .num_columns(4)
+                        .show(ui, |ui| {
+                            for l in self.lines.iter() {
+                                match l {
+                                    DisasmLine::Label(name) => {
+                                        ui.label("");
+                                        ui.strong(format!("<{}>:", name));
+                                    }
+                                    DisasmLine::Source { location, text } => {
+                                        ui.label("");
+                                        ui.weak(location);
+                                        ui.label("");
+                                        ui.weak(text.as_deref().unwrap_or(""));
+                                    }
+                                    DisasmLine::Instruction(i) => {
+                                        let b_set = breakpoints.is_set(core_idx, i.address);
+                                        let dot = if b_set { "●" } else { "○" };
+                                        if ui
+                                            .add(eframe::egui::Button::new(dot).frame(false))
+                                            .on_hover_text("toggle breakpoint")
+                                            .clicked()
+                                        {
+                                            toggle = Some(i.address);
+                                        }
+                                        let mut addr = eframe::egui::RichText::new(format!(
+                                            "{:#010x}",
+                                            i.address
+                                        ))
+                                        .monospace();
+                                        if Some(i.address) == self.pc {
+                                            addr = addr.color(ui.visuals().warn_fg_color);
+                                        }
+                                        let resp = ui.label(addr);
+                                        if self.b_scroll_to_anchor && Some(i.address) == anchor {
+                                            resp.scroll_to_me(Some(eframe::egui::Align::Center));
+                                        }
+                                        ui.monospace(
+                                            i.bytes
+                                                .iter()
+                                                .map(|b| format!("{:02x}", b))
+                                                .collect::<Vec<_>>()
+                                                .join(" "),
+                                        );
+                                        let mut text =
+                                            eframe::egui::RichText::new(&i.text).monospace();
+                                        if Some(i.address) == self.pc {
+                                            text = text.color(ui.visuals().warn_fg_color);
+                                        }
+                                        ui.label(text);
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            self.b_scroll_to_anchor = false;
+
+            if let Some(address) = toggle {
+                let location = describe_address(address, symbols, dwarf);
+                let location = if location.is_empty() {
+                    format!("{:#010x}", address)
+                } else {
+                    location
+                };
+                self.rst_info = breakpoints.toggle_at(h, core_idx, address, location);
+            }
+        }
+    }
+}
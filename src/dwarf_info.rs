@@ -0,0 +1,883 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_dwarf_info {
+    use crate::debug_opts::m_debug_opts::parse_number;
+    use gimli::{AttributeValue, EndianRcSlice, Reader, RunTimeEndian, UnwindSection};
+    use goblin::elf::{section_header, Elf};
+    use std::{collections::HashMap, error::Error, fs, path::Path, rc::Rc};
+
+    type DwarfReader = EndianRcSlice<RunTimeEndian>;
+    type Unit = gimli::Unit<DwarfReader>;
+    type Entry<'a, 'u> = gimli::DebuggingInformationEntry<'a, 'u, DwarfReader>;
+    type TreeNode<'a, 'u, 't> = gimli::EntriesTreeNode<'a, 'u, 't, DwarfReader>;
+
+    /// Types are keyed by their offset in `.debug_info`.
+    pub type TypeId = usize;
+
+    pub struct Member {
+        pub name: String,
+        pub offset: u64,
+        pub type_id: Option<TypeId>,
+    }
+
+    pub enum DwarfType {
+        Base {
+            name: String,
+            size: u64,
+            encoding: gimli::DwAte,
+        },
+        Pointer {
+            name: String,
+            size: u64,
+            target: Option<TypeId>,
+        },
+        Struct {
+            name: String,
+            size: u64,
+            members: Vec<Member>,
+        },
+        Array {
+            elem: Option<TypeId>,
+            count: Option<u64>,
+        },
+        Enum {
+            name: String,
+            size: u64,
+            variants: Vec<(String, i128)>,
+        },
+        /// Typedefs and const/volatile qualifiers.
+        Alias {
+            name: Option<String>,
+            target: Option<TypeId>,
+        },
+    }
+
+    pub struct GlobalVariable {
+        pub name: String,
+        pub scoped_name: String,
+        pub address: u64,
+        pub type_id: Option<TypeId>,
+    }
+
+    struct LineRow {
+        address: u64,
+        file: usize,
+        line: u64,
+        b_stmt: bool,
+        b_end_sequence: bool,
+    }
+
+    enum VarLocation {
+        None,
+        Expr(gimli::Expression<DwarfReader>),
+        List(Vec<(u64, u64, gimli::Expression<DwarfReader>)>),
+    }
+
+    pub struct LocalVariable {
+        pub name: String,
+        pub type_id: Option<TypeId>,
+        location: VarLocation,
+    }
+
+    pub struct Function {
+        pub name: String,
+        frame_base: Option<gimli::Expression<DwarfReader>>,
+        encoding: gimli::Encoding,
+        pub locals: Vec<LocalVariable>,
+    }
+
+    /// Register values of one stack frame, keyed by DWARF register number.
+    #[derive(Clone, Default)]
+    pub struct FrameRegisters {
+        pub regs: HashMap<u16, u64>,
+        pub cfa: Option<u64>,
+    }
+
+    /// The caller's registers as recovered from the call frame information.
+    pub struct UnwindStep {
+        pub cfa: u64,
+        pub regs: HashMap<u16, u64>,
+        pub return_address_register: u16,
+    }
+
+    #[derive(Default)]
+    pub struct DwarfInfo {
+        files: Vec<String>,
+        rows: Vec<LineRow>,
+        types: HashMap<TypeId, DwarfType>,
+        globals: Vec<GlobalVariable>,
+        functions: Vec<Function>,
+        function_ranges: Vec<(u64, u64, usize)>,
+        debug_frame: Option<gimli::DebugFrame<DwarfReader>>,
+        pub address_size: u8,
+    }
+
+    /// Name and type of an entry, looked up through `DW_AT_abstract_origin` or
+    /// `DW_AT_specification` when the entry itself doesn't carry them.
+    fn attr_name_and_type(
+        dwarf: &gimli::Dwarf<DwarfReader>,
+        unit: &Unit,
+        entry: &Entry,
+    ) -> gimli::Result<(Option<String>, Option<TypeId>)> {
+        let mut name = attr_name(dwarf, unit, entry)?;
+        let mut type_id = attr_type(unit, entry)?;
+        for at in [gimli::DW_AT_abstract_origin, gimli::DW_AT_specification] {
+            if name.is_some() && type_id.is_some() {
+                break;
+            }
+            if let Some(AttributeValue::UnitRef(o)) = entry.attr_value(at)? {
+                let origin = unit.entry(o)?;
+                if name.is_none() {
+                    name = attr_name(dwarf, unit, &origin)?;
+                }
+                if type_id.is_none() {
+                    type_id = attr_type(unit, &origin)?;
+                }
+            }
+        }
+        Ok((name, type_id))
+    }
+
+    fn attr_location(
+        dwarf: &gimli::Dwarf<DwarfReader>,
+        unit: &Unit,
+        entry: &Entry,
+    ) -> gimli::Result<VarLocation> {
+        match entry.attr_value(gimli::DW_AT_location)? {
+            Some(AttributeValue::Exprloc(e)) => Ok(VarLocation::Expr(e)),
+            Some(attr) => match dwarf.attr_locations(unit, attr)? {
+                Some(mut iter) => {
+                    let mut list = Vec::new();
+                    while let Some(l) = iter.next()? {
+                        list.push((l.range.begin, l.range.end, l.data));
+                    }
+                    Ok(VarLocation::List(list))
+                }
+                None => Ok(VarLocation::None),
+            },
+            None => Ok(VarLocation::None),
+        }
+    }
+
+    fn attr_name(
+        dwarf: &gimli::Dwarf<DwarfReader>,
+        unit: &Unit,
+        entry: &Entry,
+    ) -> gimli::Result<Option<String>> {
+        match entry.attr_value(gimli::DW_AT_name)? {
+            Some(v) => Ok(Some(
+                dwarf.attr_string(unit, v)?.to_string_lossy()?.into_owned(),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    fn attr_u64(entry: &Entry, at: gimli::DwAt) -> gimli::Result<Option<u64>> {
+        Ok(entry.attr_value(at)?.and_then(|v| v.udata_value()))
+    }
+
+    fn attr_type(unit: &Unit, entry: &Entry) -> gimli::Result<Option<TypeId>> {
+        Ok(match entry.attr_value(gimli::DW_AT_type)? {
+            Some(AttributeValue::UnitRef(o)) => o.to_debug_info_offset(&unit.header).map(|o| o.0),
+            Some(AttributeValue::DebugInfoRef(o)) => Some(o.0),
+            _ => None,
+        })
+    }
+
+    /// Returns the address of a static location, i.e. a lone `DW_OP_addr`.
+    fn attr_static_address(unit: &Unit, entry: &Entry) -> gimli::Result<Option<u64>> {
+        let Some(AttributeValue::Exprloc(expr)) = entry.attr_value(gimli::DW_AT_location)? else {
+            return Ok(None);
+        };
+        let mut ops = expr.operations(unit.encoding());
+        if let Some(gimli::Operation::Address { address }) = ops.next()? {
+            if ops.next()?.is_none() {
+                return Ok(Some(address));
+            }
+        }
+        Ok(None)
+    }
+
+    fn section_data<'a>(elf: &Elf, buffer: &'a [u8], name: &str) -> &'a [u8] {
+        elf.section_headers
+            .iter()
+            .find(|sh| {
+                sh.sh_type != section_header::SHT_NOBITS
+                    && elf.shdr_strtab.get_at(sh.sh_name) == Some(name)
+            })
+            .and_then(|sh| {
+                let start = usize::try_from(sh.sh_offset).ok()?;
+                let end = start.checked_add(usize::try_from(sh.sh_size).ok()?)?;
+                buffer.get(start..end)
+            })
+            .unwrap_or(&[])
+    }
+
+    impl DwarfInfo {
+        pub fn load(elf_file: &Path) -> Result<Self, Box<dyn Error>> {
+            let buffer = fs::read(elf_file)?;
+            let elf = Elf::parse(buffer.as_slice())?;
+            let endian = if elf.little_endian {
+                RunTimeEndian::Little
+            } else {
+                RunTimeEndian::Big
+            };
+            let dwarf = gimli::Dwarf::load(|id| -> Result<DwarfReader, gimli::Error> {
+                let data = section_data(&elf, &buffer, id.name());
+                Ok(EndianRcSlice::new(Rc::from(data), endian))
+            })?;
+
+            let mut info = Self {
+                address_size: if elf.is_64 { 8 } else { 4 },
+                ..Default::default()
+            };
+            let debug_frame = section_data(&elf, &buffer, ".debug_frame");
+            if !debug_frame.is_empty() {
+                let mut debug_frame =
+                    gimli::DebugFrame::from(EndianRcSlice::new(Rc::from(debug_frame), endian));
+                debug_frame.set_address_size(info.address_size);
+                info.debug_frame = Some(debug_frame);
+            }
+            let mut file_ids: HashMap<String, usize> = HashMap::new();
+            let mut units = dwarf.units();
+            while let Some(header) = units.next()? {
+                let unit = dwarf.unit(header)?;
+                {
+                    let mut tree = unit.entries_tree(None)?;
+                    info.parse_children(&dwarf, &unit, tree.root()?, "")?;
+                }
+                let Some(program) = unit.line_program.clone() else {
+                    continue;
+                };
+                let comp_dir = unit
+                    .comp_dir
+                    .as_ref()
+                    .map(|d| d.to_string_lossy().map(|s| s.into_owned()))
+                    .transpose()?;
+                let mut rows = program.rows();
+                while let Some((header, row)) = rows.next_row()? {
+                    let Some(file) = row.file(header) else {
+                        continue;
+                    };
+                    let mut path = std::path::PathBuf::new();
+                    if let Some(dir) = comp_dir.as_ref() {
+                        path.push(dir);
+                    }
+                    if let Some(dir) = file.directory(header) {
+                        path.push(dwarf.attr_string(&unit, dir)?.to_string_lossy()?.as_ref());
+                    }
+                    path.push(
+                        dwarf
+                            .attr_string(&unit, file.path_name())?
+                            .to_string_lossy()?
+                            .as_ref(),
+                    );
+                    let path = path.to_string_lossy().into_owned();
+                    let next_id = info.files.len();
+                    let file_id = *file_ids.entry(path.clone()).or_insert(next_id);
+                    if file_id == next_id {
+                        info.files.push(path);
+                    }
+                    info.rows.push(LineRow {
+                        address: row.address(),
+                        file: file_id,
+                        line: row.line().map_or(0, |l| l.get()),
+                        b_stmt: row.is_stmt(),
+                        b_end_sequence: row.end_sequence(),
+                    });
+                }
+            }
+            // Sequences that the linker discarded keep address 0, drop them.
+            info.rows.retain(|r| r.address != 0);
+            info.rows.sort_by_key(|r| (r.address, !r.b_end_sequence));
+            info.function_ranges.sort_by_key(|r| r.0);
+            Ok(info)
+        }
+
+        fn parse_locals(
+            &mut self,
+            dwarf: &gimli::Dwarf<DwarfReader>,
+            unit: &Unit,
+            node: TreeNode,
+            scope: &str,
+            locals: &mut Vec<LocalVariable>,
+        ) -> gimli::Result<()> {
+            let mut children = node.children();
+            while let Some(child) = children.next()? {
+                let e = child.entry();
+                match e.tag() {
+                    gimli::DW_TAG_formal_parameter | gimli::DW_TAG_variable => {
+                        let (name, type_id) = attr_name_and_type(dwarf, unit, e)?;
+                        locals.push(LocalVariable {
+                            name: name.unwrap_or_default(),
+                            type_id,
+                            location: attr_location(dwarf, unit, e)?,
+                        });
+                    }
+                    // Block scopes are flattened into the function.
+                    gimli::DW_TAG_lexical_block => {
+                        self.parse_locals(dwarf, unit, child, scope, locals)?;
+                    }
+                    // The variables of inlined calls belong to the callee.
+                    gimli::DW_TAG_inlined_subroutine => {}
+                    _ => {
+                        self.parse_node(dwarf, unit, child, scope)?;
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        fn parse_children(
+            &mut self,
+            dwarf: &gimli::Dwarf<DwarfReader>,
+            unit: &Unit,
+            node: TreeNode,
+            scope: &str,
+        ) -> gimli::Result<()> {
+            let mut children = node.children();
+            while let Some(child) = children.next()? {
+                self.parse_node(dwarf, unit, child, scope)?;
+            }
+            Ok(())
+        }
+
+        fn parse_node(
+            &mut self,
+            dwarf: &gimli::Dwarf<DwarfReader>,
+            unit: &Unit,
+            node: TreeNode,
+            scope: &str,
+        ) -> gimli::Result<()> {
+            let entry = node.entry();
+            let Some(id) = entry
+                .offset()
+                .to_debug_info_offset(&unit.header)
+                .map(|o| o.0)
+            else {
+                return Ok(());
+            };
+            let name = attr_name(dwarf, unit, entry)?;
+            let size = attr_u64(entry, gimli::DW_AT_byte_size)?;
+            let target = attr_type(unit, entry)?;
+            let tag = entry.tag();
+            match tag {
+                gimli::DW_TAG_namespace => {
+                    let scope = match name {
+                        Some(n) if !scope.is_empty() => format!("{}::{}", scope, n),
+                        Some(n) => n,
+                        None => scope.to_owned(),
+                    };
+                    self.parse_children(dwarf, unit, node, &scope)?;
+                }
+                gimli::DW_TAG_variable => {
+                    if let (Some(name), Some(address)) = (name, attr_static_address(unit, entry)?) {
+                        if address != 0 {
+                            let scoped_name = if scope.is_empty() {
+                                name.clone()
+                            } else {
+                                format!("{}::{}", scope, name)
+                            };
+                            self.globals.push(GlobalVariable {
+                                name,
+                                scoped_name,
+                                address,
+                                type_id: target,
+                            });
+                        }
+                    }
+                }
+                gimli::DW_TAG_subprogram => {
+                    let name = match name {
+                        Some(n) => Some(n),
+                        None => attr_name_and_type(dwarf, unit, entry)?.0,
+                    };
+                    let frame_base = match entry.attr_value(gimli::DW_AT_frame_base)? {
+                        Some(AttributeValue::Exprloc(e)) => Some(e),
+                        _ => None,
+                    };
+                    let mut ranges = Vec::new();
+                    let mut iter = dwarf.die_ranges(unit, entry)?;
+                    while let Some(r) = iter.next()? {
+                        if r.begin != 0 && r.begin < r.end {
+                            ranges.push(r);
+                        }
+                    }
+                    let mut locals = Vec::new();
+                    self.parse_locals(dwarf, unit, node, scope, &mut locals)?;
+                    if !ranges.is_empty() {
+                        let name = match name {
+                            Some(n) if !scope.is_empty() => format!("{}::{}", scope, n),
+                            Some(n) => n,
+                            None => "??".to_owned(),
+                        };
+                        let idx = self.functions.len();
+                        self.functions.push(Function {
+                            name,
+                            frame_base,
+                            encoding: unit.encoding(),
+                            locals,
+                        });
+                        for r in ranges {
+                            self.function_ranges.push((r.begin, r.end, idx));
+                        }
+                    }
+                }
+                gimli::DW_TAG_base_type => {
+                    let encoding = match entry.attr_value(gimli::DW_AT_encoding)? {
+                        Some(AttributeValue::Encoding(e)) => e,
+                        _ => gimli::DW_ATE_unsigned,
+                    };
+                    self.types.insert(
+                        id,
+                        DwarfType::Base {
+                            name: name.unwrap_or_default(),
+                            size: size.unwrap_or(0),
+                            encoding,
+                        },
+                    );
+                }
+                gimli::DW_TAG_pointer_type
+                | gimli::DW_TAG_reference_type
+                | gimli::DW_TAG_rvalue_reference_type => {
+                    self.types.insert(
+                        id,
+                        DwarfType::Pointer {
+                            name: name.unwrap_or_default(),
+                            size: size.unwrap_or(unit.header.address_size() as u64),
+                            target,
+                        },
+                    );
+                }
+                gimli::DW_TAG_structure_type
+                | gimli::DW_TAG_union_type
+                | gimli::DW_TAG_class_type => {
+                    let scope = name.clone().unwrap_or_default();
+                    let mut members = Vec::new();
+                    let mut children = node.children();
+                    while let Some(child) = children.next()? {
+                        let e = child.entry();
+                        if e.tag() == gimli::DW_TAG_member {
+                            members.push(Member {
+                                name: attr_name(dwarf, unit, e)?.unwrap_or_default(),
+                                offset: attr_u64(e, gimli::DW_AT_data_member_location)?
+                                    .unwrap_or(0),
+                                type_id: attr_type(unit, e)?,
+                            });
+                        } else {
+                            self.parse_node(dwarf, unit, child, &scope)?;
+                        }
+                    }
+                    self.types.insert(
+                        id,
+                        DwarfType::Struct {
+                            name: name.unwrap_or_default(),
+                            size: size.unwrap_or(0),
+                            members,
+                        },
+                    );
+                }
+                gimli::DW_TAG_array_type => {
+                    // Multi-dimensional arrays are shown flattened.
+                    let mut count = None;
+                    let mut children = node.children();
+                    while let Some(child) = children.next()? {
+                        let e = child.entry();
+                        if e.tag() != gimli::DW_TAG_subrange_type {
+                            continue;
+                        }
+                        let n = match attr_u64(e, gimli::DW_AT_count)? {
+                            Some(c) => Some(c),
+                            None => attr_u64(e, gimli::DW_AT_upper_bound)?.map(|u| u + 1),
+                        };
+                        count = match (count, n) {
+                            (None, n) => n,
+                            (Some(c), Some(n)) => Some(c * n),
+                            (Some(_), None) => None,
+                        };
+                    }
+                    self.types.insert(
+                        id,
+                        DwarfType::Array {
+                            elem: target,
+                            count,
+                        },
+                    );
+                }
+                gimli::DW_TAG_enumeration_type => {
+                    let mut variants = Vec::new();
+                    let mut children = node.children();
+                    while let Some(child) = children.next()? {
+                        let e = child.entry();
+                        if e.tag() != gimli::DW_TAG_enumerator {
+                            continue;
+                        }
+                        let value = match e.attr_value(gimli::DW_AT_const_value)? {
+                            Some(AttributeValue::Sdata(v)) => v as i128,
+                            Some(v) => v.udata_value().map_or(0, |v| v as i128),
+                            None => 0,
+                        };
+                        variants.push((attr_name(dwarf, unit, e)?.unwrap_or_default(), value));
+                    }
+                    self.types.insert(
+                        id,
+                        DwarfType::Enum {
+                            name: name.unwrap_or_default(),
+                            size: size.unwrap_or(4),
+                            variants,
+                        },
+                    );
+                }
+                gimli::DW_TAG_typedef
+                | gimli::DW_TAG_const_type
+                | gimli::DW_TAG_volatile_type
+                | gimli::DW_TAG_restrict_type
+                | gimli::DW_TAG_atomic_type => {
+                    self.types.insert(id, DwarfType::Alias { name, target });
+                }
+                _ => {}
+            }
+            Ok(())
+        }
+
+        pub fn function_at(&self, pc: u64) -> Option<&Function> {
+            let idx = self.function_ranges.partition_point(|r| r.0 <= pc);
+            self.function_ranges[..idx]
+                .iter()
+                .rev()
+                .find(|r| pc < r.1)
+                .map(|r| &self.functions[r.2])
+        }
+
+        /// Applies the `.debug_frame` rules at `lookup_pc` to recover the caller's
+        /// registers. `read_word` reads one address-sized word of target memory.
+        pub fn unwind_frame(
+            &self,
+            frame: &FrameRegisters,
+            lookup_pc: u64,
+            read_word: &mut dyn FnMut(u64) -> Option<u64>,
+        ) -> Option<UnwindStep> {
+            let debug_frame = self.debug_frame.as_ref()?;
+            let bases = gimli::BaseAddresses::default();
+            let mut ctx = gimli::UnwindContext::new();
+            let fde = debug_frame
+                .fde_for_address(&bases, lookup_pc, gimli::DebugFrame::cie_from_offset)
+                .ok()?;
+            let row = fde
+                .unwind_info_for_address(debug_frame, &bases, &mut ctx, lookup_pc)
+                .ok()?;
+            let cfa = match row.cfa() {
+                gimli::CfaRule::RegisterAndOffset { register, offset } => {
+                    frame.regs.get(&register.0)?.wrapping_add_signed(*offset)
+                }
+                _ => return None,
+            };
+            let mut regs = frame.regs.clone();
+            for (reg, rule) in row.registers() {
+                let value = match rule {
+                    gimli::RegisterRule::SameValue => frame.regs.get(&reg.0).copied(),
+                    gimli::RegisterRule::Offset(o) => read_word(cfa.wrapping_add_signed(*o)),
+                    gimli::RegisterRule::ValOffset(o) => Some(cfa.wrapping_add_signed(*o)),
+                    gimli::RegisterRule::Register(r) => frame.regs.get(&r.0).copied(),
+                    gimli::RegisterRule::Constant(c) => Some(*c),
+                    _ => None,
+                };
+                match value {
+                    Some(v) => regs.insert(reg.0, v),
+                    None => regs.remove(&reg.0),
+                };
+            }
+            Some(UnwindStep {
+                cfa,
+                regs,
+                return_address_register: fde.cie().return_address_register().0,
+            })
+        }
+
+        fn evaluate(
+            &self,
+            expr: &gimli::Expression<DwarfReader>,
+            encoding: gimli::Encoding,
+            frame: &FrameRegisters,
+            frame_base: Option<u64>,
+            read: &mut dyn FnMut(u64, usize) -> Option<Vec<u8>>,
+        ) -> Result<Vec<gimli::Piece<DwarfReader>>, String> {
+            let mut eval = expr.clone().evaluation(encoding);
+            let mut result = eval.evaluate().map_err(|e| format!("{}", e))?;
+            loop {
+                result = match result {
+                    gimli::EvaluationResult::Complete => break,
+                    gimli::EvaluationResult::RequiresMemory { address, size, .. } => {
+                        let bytes = read(address, size as usize)
+                            .ok_or(format!("can't read {:#010x}", address))?;
+                        let mut b = [0u8; 8];
+                        let n = bytes.len().min(8);
+                        b[..n].copy_from_slice(&bytes[..n]);
+                        eval.resume_with_memory(gimli::Value::Generic(u64::from_le_bytes(b)))
+                    }
+                    gimli::EvaluationResult::RequiresRegister { register, .. } => {
+                        let v = frame
+                            .regs
+                            .get(&register.0)
+                            .ok_or(format!("register {} not available", register.0))?;
+                        eval.resume_with_register(gimli::Value::Generic(*v))
+                    }
+                    gimli::EvaluationResult::RequiresFrameBase => {
+                        eval.resume_with_frame_base(frame_base.ok_or("no frame base")?)
+                    }
+                    gimli::EvaluationResult::RequiresCallFrameCfa => {
+                        eval.resume_with_call_frame_cfa(frame.cfa.ok_or("no CFA")?)
+                    }
+                    gimli::EvaluationResult::RequiresRelocatedAddress(a) => {
+                        eval.resume_with_relocated_address(a)
+                    }
+                    _ => return Err("unsupported DWARF expression".to_owned()),
+                }
+                .map_err(|e| format!("{}", e))?;
+            }
+            Ok(eval.result())
+        }
+
+        /// Reads the current value of a local variable of `function` in `frame`.
+        pub fn read_local(
+            &self,
+            function: &Function,
+            var: &LocalVariable,
+            frame: &FrameRegisters,
+            pc: u64,
+            read: &mut dyn FnMut(u64, usize) -> Option<Vec<u8>>,
+        ) -> Result<Vec<u8>, String> {
+            let expr = match &var.location {
+                VarLocation::Expr(e) => e,
+                VarLocation::List(list) => {
+                    match list
+                        .iter()
+                        .find(|(begin, end, _)| *begin <= pc && pc < *end)
+                    {
+                        Some((_, _, e)) => e,
+                        None => return Err("optimized out".to_owned()),
+                    }
+                }
+                VarLocation::None => return Err("optimized out".to_owned()),
+            };
+            let frame_base = match function.frame_base.as_ref() {
+                Some(fb) => {
+                    let pieces = self.evaluate(fb, function.encoding, frame, None, read)?;
+                    match pieces.first().map(|p| &p.location) {
+                        Some(gimli::Location::Register { register }) => {
+                            frame.regs.get(&register.0).copied()
+                        }
+                        Some(gimli::Location::Address { address }) => Some(*address),
+                        _ => None,
+                    }
+                }
+                None => None,
+            };
+            let size = self.type_size(var.type_id) as usize;
+            let pieces = self.evaluate(expr, function.encoding, frame, frame_base, read)?;
+            let mut bytes = Vec::new();
+            for p in pieces {
+                let n = p.size_in_bits.map_or(size, |b| b.div_ceil(8) as usize);
+                let mut piece = match p.location {
+                    gimli::Location::Register { register } => frame
+                        .regs
+                        .get(&register.0)
+                        .ok_or(format!("register {} not available", register.0))?
+                        .to_le_bytes()
+                        .to_vec(),
+                    gimli::Location::Address { address } => {
+                        read(address, n).ok_or(format!("can't read {:#010x}", address))?
+                    }
+                    gimli::Location::Value { value } => value
+                        .to_u64(u64::MAX)
+                        .map_err(|e| format!("{}", e))?
+                        .to_le_bytes()
+                        .to_vec(),
+                    gimli::Location::Bytes { value } => {
+                        value.to_slice().map_err(|e| format!("{}", e))?.into_owned()
+                    }
+                    gimli::Location::Empty => return Err("optimized out".to_owned()),
+                    _ => return Err("unsupported location".to_owned()),
+                };
+                piece.resize(n, 0);
+                bytes.extend(piece);
+            }
+            Ok(bytes)
+        }
+
+        pub fn globals(&self) -> &[GlobalVariable] {
+            &self.globals
+        }
+
+        pub fn lookup_global(&self, name: &str) -> Option<&GlobalVariable> {
+            let name = name.trim();
+            self.globals
+                .iter()
+                .find(|g| g.scoped_name == name)
+                .or_else(|| self.globals.iter().find(|g| g.name == name))
+        }
+
+        /// Resolves a global followed by `.field` and `[index]` accessors, e.g.
+        /// `foo.bar[3]`, to its name, address and type. Multi-dimensional arrays
+        /// are indexed flattened.
+        pub fn resolve_expression(
+            &self,
+            expr: &str,
+        ) -> Result<(String, u64, Option<TypeId>), String> {
+            let expr = expr.trim();
+            let end = expr.find(['.', '[']).unwrap_or(expr.len());
+            let base = expr[..end].trim();
+            let g = self
+                .lookup_global(base)
+                .ok_or(format!("no global variable named {}", base))?;
+            let mut name = g.scoped_name.clone();
+            let (mut address, mut type_id) = (g.address, g.type_id);
+            let mut rest = expr[end..].trim_start();
+            while !rest.is_empty() {
+                if let Some(r) = rest.strip_prefix('.') {
+                    let len = r.find(['.', '[']).unwrap_or(r.len());
+                    let field = r[..len].trim();
+                    let Some(DwarfType::Struct { members, .. }) = self.resolve_type(type_id) else {
+                        return Err(format!("{} isn't a struct", name));
+                    };
+                    let m = members
+                        .iter()
+                        .find(|m| m.name == field)
+                        .ok_or(format!("{} has no field {}", name, field))?;
+                    address = address
+                        .checked_add(m.offset)
+                        .ok_or(format!("{}.{} overflows the address space", name, field))?;
+                    type_id = m.type_id;
+                    name = format!("{}.{}", name, field);
+                    rest = r[len..].trim_start();
+                } else if let Some(r) = rest.strip_prefix('[') {
+                    let close = r.find(']').ok_or(format!("missing ] in {}", expr))?;
+                    let idx = parse_number(&r[..close])
+                        .and_then(|v| u64::try_from(v).ok())
+                        .ok_or(format!("invalid index {}", r[..close].trim()))?;
+                    let Some(DwarfType::Array { elem, count }) = self.resolve_type(type_id) else {
+                        return Err(format!("{} isn't an array", name));
+                    };
+                    if count.is_some_and(|c| idx >= c) {
+                        return Err(format!("index {} out of bounds of {}", idx, name));
+                    }
+                    address = idx
+                        .checked_mul(self.type_size(*elem))
+                        .and_then(|o| address.checked_add(o))
+                        .ok_or(format!("{}[{}] overflows the address space", name, idx))?;
+                    type_id = *elem;
+                    name = format!("{}[{}]", name, idx);
+                    rest = r[close + 1..].trim_start();
+                } else {
+                    return Err(format!("unexpected {} in {}", rest, expr));
+                }
+            }
+            Ok((name, address, type_id))
+        }
+
+        /// Follows typedefs and qualifiers down to the underlying type.
+        pub fn resolve_type(&self, type_id: Option<TypeId>) -> Option<&DwarfType> {
+            let mut id = type_id?;
+            // Bounded, a broken file must not hang the UI.
+            for _ in 0..32 {
+                match self.types.get(&id)? {
+                    DwarfType::Alias { target, .. } => id = (*target)?,
+                    t => return Some(t),
+                }
+            }
+            None
+        }
+
+        pub fn type_size(&self, type_id: Option<TypeId>) -> u64 {
+            match self.resolve_type(type_id) {
+                Some(DwarfType::Base { size, .. })
+                | Some(DwarfType::Pointer { size, .. })
+                | Some(DwarfType::Struct { size, .. })
+                | Some(DwarfType::Enum { size, .. }) => *size,
+                Some(DwarfType::Array { elem, count }) => {
+                    self.type_size(*elem).saturating_mul(count.unwrap_or(0))
+                }
+                _ => 0,
+            }
+        }
+
+        pub fn type_name(&self, type_id: Option<TypeId>) -> String {
+            let Some(id) = type_id else {
+                return "void".to_owned();
+            };
+            match self.types.get(&id) {
+                Some(DwarfType::Base { name, .. })
+                | Some(DwarfType::Struct { name, .. })
+                | Some(DwarfType::Enum { name, .. })
+                    if !name.is_empty() =>
+                {
+                    name.clone()
+                }
+                Some(DwarfType::Pointer { name, .. }) if !name.is_empty() => name.clone(),
+                Some(DwarfType::Pointer { target, .. }) => format!("{}*", self.type_name(*target)),
+                Some(DwarfType::Array { elem, count }) => match count {
+                    Some(c) => format!("{}[{}]", self.type_name(*elem), c),
+                    None => format!("{}[]", self.type_name(*elem)),
+                },
+                Some(DwarfType::Alias {
+                    name: Some(name), ..
+                }) => name.clone(),
+                Some(DwarfType::Alias { name: None, target }) => self.type_name(*target),
+                _ => "?".to_owned(),
+            }
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.rows.is_empty()
+        }
+
+        /// Returns the source file and line that generated the code at `address`.
+        pub fn location_at(&self, address: u64) -> Option<(&str, u64)> {
+            let idx = self.rows.partition_point(|r| r.address <= address);
+            let row = self.rows.get(idx.checked_sub(1)?)?;
+            if row.b_end_sequence || row.line == 0 {
+                return None;
+            }
+            Some((self.files[row.file].as_str(), row.line))
+        }
+
+        /// Resolves `file:line` to the lowest statement address. `file` may be any
+        /// trailing part of the path; lines without code move to the next one that
+        /// has some, like gdb does.
+        pub fn address_of_line(&self, file: &str, line: u64) -> Option<u64> {
+            let file = file.trim().replace('\\', "/");
+            let matches_file = |f: &str| {
+                let f = f.replace('\\', "/");
+                f == file || f.ends_with(&format!("/{}", file))
+            };
+            let candidates: Vec<&LineRow> = self
+                .rows
+                .iter()
+                .filter(|r| r.b_stmt && !r.b_end_sequence && r.line >= line)
+                .filter(|r| matches_file(&self.files[r.file]))
+                .collect();
+            let best_line = candidates.iter().map(|r| r.line).min()?;
+            candidates
+                .iter()
+                .filter(|r| r.line == best_line)
+                .map(|r| r.address)
+                .min()
+        }
+    }
+}
@@ -0,0 +1,91 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_elf_symbols {
+    use goblin::elf::{header, sym, Elf};
+    use std::{error::Error, fs, path::Path};
+
+    pub struct ElfSymbol {
+        pub name: String,
+        pub raw_name: String,
+        pub address: u64,
+        pub size: u64,
+        pub b_func: bool,
+    }
+
+    #[derive(Default)]
+    pub struct ElfSymbols {
+        symbols: Vec<ElfSymbol>,
+    }
+
+    impl ElfSymbols {
+        pub fn load(elf_file: &Path) -> Result<Self, Box<dyn Error>> {
+            let buffer = fs::read(elf_file)?;
+            let binary = Elf::parse(buffer.as_slice())?;
+            let b_thumb = binary.header.e_machine == header::EM_ARM;
+            let mut symbols = Vec::new();
+            for s in &binary.syms {
+                let b_func = s.st_type() == sym::STT_FUNC;
+                if !(b_func || s.st_type() == sym::STT_OBJECT) || s.st_value == 0 {
+                    continue;
+                }
+                let Some(raw_name) = binary.strtab.get_at(s.st_name) else {
+                    continue;
+                };
+                // Thumb function symbols carry the mode in bit 0.
+                let address = if b_func && b_thumb {
+                    s.st_value & !1
+                } else {
+                    s.st_value
+                };
+                symbols.push(ElfSymbol {
+                    name: format!("{:#}", rustc_demangle::demangle(raw_name)),
+                    raw_name: raw_name.to_owned(),
+                    address,
+                    size: s.st_size,
+                    b_func,
+                });
+            }
+            symbols.sort_by_key(|s| s.address);
+            Ok(Self { symbols })
+        }
+
+        /// Looks a symbol up by its demangled or raw name.
+        pub fn lookup(&self, name: &str) -> Option<&ElfSymbol> {
+            let name = name.trim();
+            self.symbols
+                .iter()
+                .find(|s| s.name == name || s.raw_name == name)
+        }
+
+        /// Data objects, i.e. the global and static variables.
+        pub fn variables(&self) -> impl Iterator<Item = &ElfSymbol> {
+            self.symbols.iter().filter(|s| !s.b_func)
+        }
+
+        /// Returns the symbol covering `address` and the offset into it.
+        pub fn symbol_at(&self, address: u64) -> Option<(&ElfSymbol, u64)> {
+            let idx = self.symbols.partition_point(|s| s.address <= address);
+            self.symbols[..idx]
+                .iter()
+                .rev()
+                .find(|s| address < s.address + s.size.max(1))
+                .map(|s| (s, address - s.address))
+        }
+    }
+}
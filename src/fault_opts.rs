@@ -0,0 +1,306 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_fault_opts {
+    use crate::breakpoint_opts::m_breakpoint_opts::describe_address;
+    use crate::call_stack_opts::m_call_stack_opts::{unwind, ExceptionFrame};
+    use crate::configuration::m_config;
+    use crate::dwarf_info::m_dwarf_info::DwarfInfo;
+    use crate::elf_symbols::m_elf_symbols::ElfSymbols;
+    use crate::probe_rs_invoke::probe_rs_integration::{FaultStatus, ProbeRsHandler};
+    use probe_rs::RegisterRole;
+    use std::time::Duration;
+
+    const CFSR_BITS: [(u32, &str, &str); 20] = [
+        (
+            0,
+            "IACCVIOL",
+            "instruction fetch from a location the MPU doesn't allow",
+        ),
+        (
+            1,
+            "DACCVIOL",
+            "data access to a location the MPU doesn't allow",
+        ),
+        (
+            3,
+            "MUNSTKERR",
+            "MPU fault while unstacking on exception return",
+        ),
+        (4, "MSTKERR", "MPU fault while stacking on exception entry"),
+        (
+            5,
+            "MLSPERR",
+            "MPU fault during lazy floating point state preservation",
+        ),
+        (7, "MMARVALID", "MMFAR holds the faulting address"),
+        (8, "IBUSERR", "bus error on instruction fetch"),
+        (
+            9,
+            "PRECISERR",
+            "precise data bus error, the stacked PC points at the access",
+        ),
+        (
+            10,
+            "IMPRECISERR",
+            "imprecise data bus error, the stacked PC is after the access",
+        ),
+        (
+            11,
+            "UNSTKERR",
+            "bus fault while unstacking on exception return",
+        ),
+        (12, "STKERR", "bus fault while stacking on exception entry"),
+        (
+            13,
+            "LSPERR",
+            "bus fault during lazy floating point state preservation",
+        ),
+        (15, "BFARVALID", "BFAR holds the faulting address"),
+        (16, "UNDEFINSTR", "undefined instruction"),
+        (
+            17,
+            "INVSTATE",
+            "invalid state, e.g. branching to an address without the Thumb bit",
+        ),
+        (18, "INVPC", "invalid EXC_RETURN value loaded into the PC"),
+        (
+            19,
+            "NOCP",
+            "coprocessor access while it's disabled, e.g. the FPU",
+        ),
+        (20, "STKOF", "stack pointer went past its limit register"),
+        (
+            24,
+            "UNALIGNED",
+            "unaligned access with alignment trapping enabled",
+        ),
+        (25, "DIVBYZERO", "divide by zero with trapping enabled"),
+    ];
+
+    const HFSR_BITS: [(u32, &str, &str); 3] = [
+        (1, "VECTTBL", "bus fault reading the vector table"),
+        (30, "FORCED", "a configurable fault escalated, see CFSR"),
+        (
+            31,
+            "DEBUGEVT",
+            "debug event while halting debug is disabled",
+        ),
+    ];
+
+    const CFSR_MMARVALID: u32 = 1 << 7;
+    const CFSR_BFARVALID: u32 = 1 << 15;
+
+    pub fn exception_name(number: u64) -> String {
+        match number {
+            0 => "thread mode".to_owned(),
+            2 => "NMI".to_owned(),
+            3 => "HardFault".to_owned(),
+            4 => "MemManage".to_owned(),
+            5 => "BusFault".to_owned(),
+            6 => "UsageFault".to_owned(),
+            7 => "SecureFault".to_owned(),
+            11 => "SVCall".to_owned(),
+            14 => "PendSV".to_owned(),
+            15 => "SysTick".to_owned(),
+            n if n >= 16 => format!("IRQ {}", n - 16),
+            n => format!("exception {}", n),
+        }
+    }
+
+    fn decode(value: u32, bits: &[(u32, &'static str, &'static str)]) -> Vec<String> {
+        bits.iter()
+            .filter(|(b, _, _)| value & (1 << b) != 0)
+            .map(|(_, name, text)| format!("{}: {}", name, text))
+            .collect()
+    }
+
+    struct FaultReport {
+        status: FaultStatus,
+        causes: Vec<String>,
+        exception: Option<String>,
+        frame: Option<ExceptionFrame>,
+        fault_location: Option<String>,
+        return_location: Option<String>,
+    }
+
+    #[derive(Default)]
+    pub struct FaultView {
+        report: Option<FaultReport>,
+        rst_info: Option<String>,
+    }
+
+    impl FaultView {
+        fn diagnose(
+            &mut self,
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            symbols: &ElfSymbols,
+            dwarf: &DwarfInfo,
+        ) -> Result<FaultReport, String> {
+            h.halt_core(
+                core_idx,
+                Duration::from_millis(m_config::CORE_HALT_TIMEOUT_MS),
+            )
+            .map_err(|e| format!("{}", e))?;
+            let status = h
+                .read_fault_status(core_idx)
+                .map_err(|e| format!("{}", e))?;
+
+            let mut causes = decode(status.cfsr, &CFSR_BITS);
+            causes.extend(decode(status.hfsr, &HFSR_BITS));
+
+            let exception = h
+                .read_core_registers(core_idx)
+                .map_err(|e| format!("{}", e))?
+                .iter()
+                .find(|(r, _)| r.register_has_role(RegisterRole::ProcessorStatus))
+                .and_then(|(_, v)| *v)
+                .map(|xpsr| exception_name(xpsr as u64 & 0x1FF));
+
+            // The innermost exception frame belongs to the code that faulted.
+            let frames = unwind(h, core_idx, symbols, dwarf)?;
+            let faulted = frames.iter().find(|f| f.exception.is_some());
+            let frame = faulted.and_then(|f| f.exception.clone());
+            Ok(FaultReport {
+                status,
+                causes,
+                exception,
+                fault_location: faulted
+                    .map(|f| format!("{:#010x} {}", f.pc, describe_address(f.pc, symbols, dwarf))),
+                return_location: frame.as_ref().map(|f| {
+                    let lr = f.lr & !1;
+                    format!("{:#010x} {}", f.lr, describe_address(lr, symbols, dwarf))
+                }),
+                frame,
+            })
+        }
+
+        pub fn ui(
+            &mut self,
+            ui: &mut eframe::egui::Ui,
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            symbols: &ElfSymbols,
+            dwarf: &DwarfInfo,
+        ) {
+            ui.horizontal(|ui| {
+                if ui.button("Diagnose fault").clicked() {
+                    match self.diagnose(h, core_idx, symbols, dwarf) {
+                        Ok(r) => {
+                            self.report = Some(r);
+                            self.rst_info.take();
+                        }
+                        Err(e) => {
+                            self.report = None;
+                            self.rst_info = Some(e);
+                        }
+                    }
+                }
+                if dwarf.is_empty() {
+                    ui.label("select an elf file with debug info to map addresses to source");
+                }
+            });
+            if let Some(info) = self.rst_info.as_ref() {
+                ui.label(info);
+            }
+            let Some(r) = self.report.as_ref() else {
+                return;
+            };
+
+            ui.separator();
+            eframe::egui::ScrollArea::vertical()
+                .auto_shrink(false)
+                .show(ui, |ui| {
+                    if let Some(e) = r.exception.as_ref() {
+                        ui.label(format!("active exception: {}", e));
+                    }
+                    if r.status.b_available {
+                        eframe::egui::Grid::new("debug_fault_status")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label("CFSR");
+                                ui.monospace(format!("{:#010x}", r.status.cfsr));
+                                ui.end_row();
+                                ui.label("HFSR");
+                                ui.monospace(format!("{:#010x}", r.status.hfsr));
+                                ui.end_row();
+                                if r.status.cfsr & CFSR_MMARVALID != 0 {
+                                    ui.label("MMFAR");
+                                    ui.monospace(format!("{:#010x}", r.status.mmfar));
+                                    ui.end_row();
+                                }
+                                if r.status.cfsr & CFSR_BFARVALID != 0 {
+                                    ui.label("BFAR");
+                                    ui.monospace(format!("{:#010x}", r.status.bfar));
+                                    ui.end_row();
+                                }
+                            });
+                        if r.causes.is_empty() {
+                            ui.label("no fault status bits are set");
+                        }
+                        for c in r.causes.iter() {
+                            ui.colored_label(ui.visuals().warn_fg_color, c);
+                        }
+                    } else {
+                        ui.label("this core has no fault status registers (ARMv6-M)");
+                    }
+
+                    ui.separator();
+                    let Some(f) = r.frame.as_ref() else {
+                        ui.label("no exception frame found on the stack");
+                        return;
+                    };
+                    if let Some(l) = r.fault_location.as_ref() {
+                        ui.label(format!("faulting pc: {}", l));
+                    }
+                    if let Some(l) = r.return_location.as_ref() {
+                        ui.label(format!("caller (lr): {}", l));
+                    }
+                    ui.label(format!(
+                        "exception frame at {:#010x} on the {} stack (EXC_RETURN {:#010x})",
+                        f.address,
+                        if f.exc_return & (1 << 2) != 0 {
+                            "process"
+                        } else {
+                            "main"
+                        },
+                        f.exc_return
+                    ));
+                    eframe::egui::Grid::new("debug_fault_frame")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (name, value) in [
+                                ("r0", f.r0),
+                                ("r1", f.r1),
+                                ("r2", f.r2),
+                                ("r3", f.r3),
+                                ("r12", f.r12),
+                                ("lr", f.lr),
+                                ("pc", f.pc),
+                                ("xpsr", f.xpsr),
+                            ] {
+                                ui.label(name);
+                                ui.monospace(format!("{:#010x}", value));
+                                ui.end_row();
+                            }
+                        });
+                });
+        }
+    }
+}
@@ -17,6 +17,9 @@
  */
 
 pub mod m_flash_opts {
+    use crate::chip_info_opts::m_chip_info_opts::ChipInfoView;
+    use crate::custom_targets::m_custom_targets::{CustomTarget, CustomTargetsView};
+    use crate::probe_opts::m_probe_opts::{chip_detection_ui, probe_config_ui, unlock_ui};
     use crate::probe_rs_invoke::probe_rs_integration::ProbeRsHandler;
     use egui_file::FileDialog;
     use probe_rs::flashing;
@@ -30,14 +33,24 @@ pub mod m_flash_opts {
         probe_selected_idx: usize,
         probe_rs_handler: Option<ProbeRsHandler>,
         target_chip_name: String,
+        b_unlock_confirmed: bool,
+        chip_info: ChipInfoView,
         file_format_selected: flashing::Format,
         dowmload_rst_info: Option<String>,
         file_dialog: Option<FileDialog>,
         selected_file: Option<PathBuf>,
         filter_s: String,
+        custom_targets: CustomTargetsView,
     }
 
     impl FlashProgram {
+        pub fn new(custom_targets: Vec<CustomTarget>) -> Self {
+            Self {
+                custom_targets: CustomTargetsView::new(custom_targets),
+                ..Default::default()
+            }
+        }
+
         pub fn ui(&mut self, ctx: &eframe::egui::Context, ui: &mut eframe::egui::Ui) {
             if self.probe_rs_handler.borrow_mut().is_none() {
                 self.probe_rs_handler = Some(ProbeRsHandler::default());
@@ -68,18 +81,17 @@ pub mod m_flash_opts {
                         if ui.button("refresh").clicked() {
                             h.get_probes_list();
                         }
+                        probe_config_ui(ui, h);
                     }
                 });
                 if let Some(h) = self.probe_rs_handler.borrow_mut() {
-                    if h.chips_list.is_empty() {
-                        h.get_availabe_chips();
-                    }
+                    h.get_availabe_chips();
                 }
 
                 ui.horizontal(|ui| {
                     if let Some(h) = self.probe_rs_handler.borrow_mut() {
                         eframe::egui::ComboBox::from_label("target")
-                            .selected_text(self.target_chip_name.to_string())
+                            .selected_text(h.chip_label(&self.target_chip_name))
                             .show_ui(ui, |ui| {
                                 for t in h.chips_list.iter() {
                                     if !self.filter_s.is_empty() {
@@ -87,14 +99,14 @@ pub mod m_flash_opts {
                                             ui.selectable_value(
                                                 &mut self.target_chip_name,
                                                 t.to_string(),
-                                                t,
+                                                h.chip_label(t),
                                             );
                                         }
                                     } else {
                                         ui.selectable_value(
                                             &mut self.target_chip_name,
                                             t.to_string(),
-                                            t,
+                                            h.chip_label(t),
                                         );
                                     }
                                 }
@@ -148,6 +160,22 @@ pub mod m_flash_opts {
                         }
                     }
                 });
+                if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                    self.chip_info.ui(ui, &self.target_chip_name);
+                    chip_detection_ui(ui, h, self.probe_selected_idx, &mut self.target_chip_name);
+                    if unlock_ui(
+                        ui,
+                        h,
+                        &mut self.b_unlock_confirmed,
+                        self.probe_selected_idx,
+                        &self.target_chip_name,
+                    ) {
+                        self.dowmload_rst_info.take();
+                    }
+                }
+                eframe::egui::CollapsingHeader::new("custom targets").show(ui, |ui| {
+                    self.custom_targets.ui(ctx, ui);
+                });
 
                 if ui.button("Select file").clicked() {
                     // Open the file dialog to select a file.
@@ -0,0 +1,580 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+/// GDB remote serial protocol server. The socket is handled on its own thread,
+/// while the requests are answered on the UI thread with the page's own session,
+/// in between RTT polls.
+pub mod m_gdb_server {
+    use crate::configuration::m_config;
+    use crate::probe_rs_invoke::probe_rs_integration::{ProbeRsHandler, RegisterSnapshot};
+    use probe_rs::{CoreStatus, CoreType};
+    use std::{
+        error::Error,
+        io::{ErrorKind, Read, Write},
+        net::{SocketAddr, TcpListener, TcpStream},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            mpsc::{self, Receiver, Sender, TryRecvError},
+            Arc,
+        },
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    const RISCV_REG_NAMES: [&str; 32] = [
+        "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "fp", "s1", "a0", "a1", "a2", "a3", "a4",
+        "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4",
+        "t5", "t6",
+    ];
+    const RISCV_PC_ID: u16 = 0x7b1;
+
+    enum GdbEvent {
+        Connected(SocketAddr),
+        Packet(String),
+        Interrupt,
+        Disconnected,
+    }
+
+    fn checksum(data: &[u8]) -> u8 {
+        data.iter().fold(0u8, |a, b| a.wrapping_add(*b))
+    }
+
+    fn serve_client(
+        mut stream: TcpStream,
+        stop: &AtomicBool,
+        events: &Sender<GdbEvent>,
+        replies: &Receiver<String>,
+    ) -> std::io::Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(Duration::from_millis(m_config::GDB_POLL_MS)))?;
+        stream.set_nodelay(true)?;
+        // Anything left over was meant for the previous client.
+        while replies.try_recv().is_ok() {}
+
+        let mut pending = Vec::new();
+        let mut buf = [0u8; 4096];
+        while !stop.load(Ordering::Relaxed) {
+            match stream.read(&mut buf) {
+                Ok(0) => return Ok(()),
+                Ok(n) => pending.extend_from_slice(&buf[..n]),
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+                Err(e) => return Err(e),
+            }
+            loop {
+                match pending.first() {
+                    None => break,
+                    Some(0x03) => {
+                        pending.remove(0);
+                        let _ = events.send(GdbEvent::Interrupt);
+                    }
+                    Some(b'$') => {
+                        let Some(hash) = pending.iter().position(|b| *b == b'#') else {
+                            break;
+                        };
+                        if pending.len() < hash + 3 {
+                            break;
+                        }
+                        let data = pending[1..hash].to_vec();
+                        let sum = std::str::from_utf8(&pending[hash + 1..hash + 3])
+                            .ok()
+                            .and_then(|s| u8::from_str_radix(s, 16).ok());
+                        pending.drain(..hash + 3);
+                        if sum != Some(checksum(&data)) {
+                            stream.write_all(b"-")?;
+                            continue;
+                        }
+                        stream.write_all(b"+")?;
+                        let _ = events.send(GdbEvent::Packet(
+                            String::from_utf8_lossy(&data).into_owned(),
+                        ));
+                    }
+                    // Acks and noise between packets.
+                    Some(_) => {
+                        pending.remove(0);
+                    }
+                }
+            }
+            while let Ok(r) = replies.try_recv() {
+                write!(stream, "${}#{:02x}", r, checksum(r.as_bytes()))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn serve(
+        listener: TcpListener,
+        stop: Arc<AtomicBool>,
+        events: Sender<GdbEvent>,
+        replies: Receiver<String>,
+    ) {
+        while !stop.load(Ordering::Relaxed) {
+            match listener.accept() {
+                Ok((stream, peer)) => {
+                    let _ = events.send(GdbEvent::Connected(peer));
+                    let _ = serve_client(stream, &stop, &events, &replies);
+                    let _ = events.send(GdbEvent::Disconnected);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    thread::sleep(Duration::from_millis(m_config::GDB_POLL_MS));
+                }
+                Err(_) => break,
+            }
+        }
+    }
+
+    fn to_hex(data: &[u8]) -> String {
+        data.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn from_hex(text: &str) -> Option<Vec<u8>> {
+        (0..text.len() / 2 * 2)
+            .step_by(2)
+            .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+            .collect()
+    }
+
+    fn parse_hex(text: &str) -> Option<u64> {
+        u64::from_str_radix(text, 16).ok()
+    }
+
+    /// Registers in the order of the target description: (gdb name, probe-rs id).
+    fn gdb_registers(core_type: CoreType) -> Option<Vec<(String, u16)>> {
+        match core_type {
+            CoreType::Riscv => {
+                let mut regs: Vec<(String, u16)> = RISCV_REG_NAMES
+                    .iter()
+                    .enumerate()
+                    .map(|(i, n)| ((*n).to_owned(), 0x1000 + i as u16))
+                    .collect();
+                regs.push(("pc".to_owned(), RISCV_PC_ID));
+                Some(regs)
+            }
+            t if t.is_cortex_m() => {
+                let mut regs: Vec<(String, u16)> =
+                    (0..13).map(|i| (format!("r{}", i), i as u16)).collect();
+                regs.push(("sp".to_owned(), 13));
+                regs.push(("lr".to_owned(), 14));
+                regs.push(("pc".to_owned(), 15));
+                regs.push(("xpsr".to_owned(), 16));
+                Some(regs)
+            }
+            _ => None,
+        }
+    }
+
+    fn target_xml(core_type: CoreType, bits: usize) -> Option<String> {
+        let regs = gdb_registers(core_type)?;
+        let (arch, feature) = if core_type == CoreType::Riscv {
+            (
+                format!("riscv:rv{}", bits),
+                "org.gnu.gdb.riscv.cpu".to_owned(),
+            )
+        } else {
+            ("arm".to_owned(), "org.gnu.gdb.arm.m-profile".to_owned())
+        };
+        let mut xml = format!(
+            "<?xml version=\"1.0\"?><target version=\"1.0\"><architecture>{}</architecture><feature name=\"{}\">",
+            arch, feature
+        );
+        for (name, _) in regs {
+            let kind = match name.as_str() {
+                "sp" => " type=\"data_ptr\"",
+                "pc" => " type=\"code_ptr\"",
+                _ => "",
+            };
+            xml += &format!("<reg name=\"{}\" bitsize=\"{}\"{}/>", name, bits, kind);
+        }
+        xml += "</feature></target>";
+        Some(xml)
+    }
+
+    #[derive(Default)]
+    pub struct GdbServer {
+        port: u16,
+        stop: Arc<AtomicBool>,
+        thread: Option<JoinHandle<()>>,
+        events: Option<Receiver<GdbEvent>>,
+        replies: Option<Sender<String>>,
+        peer: Option<SocketAddr>,
+        /// Set while the core runs on behalf of the client, the stop reply is due when
+        /// it halts.
+        b_running: bool,
+        breakpoints: Vec<u64>,
+        rst_info: Option<String>,
+    }
+
+    impl Drop for GdbServer {
+        fn drop(&mut self) {
+            self.stop_server();
+        }
+    }
+
+    impl GdbServer {
+        fn start_server(&mut self) -> Result<(), Box<dyn Error>> {
+            let listener = TcpListener::bind(("127.0.0.1", self.port))?;
+            listener.set_nonblocking(true)?;
+            let (event_tx, event_rx) = mpsc::channel();
+            let (reply_tx, reply_rx) = mpsc::channel();
+            let stop = Arc::new(AtomicBool::new(false));
+            let thread_stop = stop.clone();
+            self.thread = Some(thread::spawn(move || {
+                serve(listener, thread_stop, event_tx, reply_rx)
+            }));
+            self.stop = stop;
+            self.events = Some(event_rx);
+            self.replies = Some(reply_tx);
+            Ok(())
+        }
+
+        fn stop_server(&mut self) {
+            self.stop.store(true, Ordering::Relaxed);
+            if let Some(t) = self.thread.take() {
+                let _ = t.join();
+            }
+            self.events = None;
+            self.replies = None;
+            self.peer = None;
+            self.b_running = false;
+        }
+
+        fn reply(&self, r: &str) {
+            if let Some(tx) = self.replies.as_ref() {
+                let _ = tx.send(r.to_owned());
+            }
+        }
+
+        /// Removes the breakpoints the client left behind.
+        fn clear_breakpoints(&mut self, h: &mut ProbeRsHandler, core_idx: usize) {
+            for b in self.breakpoints.drain(..) {
+                let _ = h.clear_breakpoint(core_idx, b);
+            }
+        }
+
+        fn read_registers(
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+        ) -> Result<(CoreType, RegisterSnapshot), Box<dyn Error>> {
+            let core_type = h.core_type(core_idx)?.ok_or("no core attached")?;
+            Ok((core_type, h.read_core_registers(core_idx)?))
+        }
+
+        fn register_hex(snapshot: &RegisterSnapshot, id: u16, bits: usize) -> String {
+            match snapshot.iter().find(|(r, _)| r.id().0 == id) {
+                Some((_, Some(v))) => to_hex(&v.to_le_bytes()[..bits / 8]),
+                _ => "xx".repeat(bits / 8),
+            }
+        }
+
+        fn register_bits(snapshot: &RegisterSnapshot, core_type: CoreType) -> usize {
+            if core_type == CoreType::Riscv {
+                snapshot
+                    .iter()
+                    .find(|(r, _)| r.id().0 == 0x1001)
+                    .map_or(32, |(r, _)| r.size_in_bits())
+            } else {
+                32
+            }
+        }
+
+        fn write_register(
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            snapshot: &RegisterSnapshot,
+            id: u16,
+            hex: &str,
+        ) -> Result<(), Box<dyn Error>> {
+            let (reg, _) = snapshot
+                .iter()
+                .find(|(r, _)| r.id().0 == id)
+                .ok_or("unknown register")?;
+            let mut bytes = from_hex(hex).ok_or("bad register value")?;
+            bytes.resize(16, 0);
+            let value = u128::from_le_bytes(bytes.try_into().map_err(|_| "bad register value")?);
+            h.write_core_register(core_idx, reg, value)
+        }
+
+        fn monitor(
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            command: &str,
+        ) -> Result<String, Box<dyn Error>> {
+            let timeout = Duration::from_millis(m_config::CORE_HALT_TIMEOUT_MS);
+            match command.trim() {
+                "reset" | "reset halt" => h.reset_and_halt_core(core_idx, timeout)?,
+                "halt" => h.halt_core(core_idx, timeout)?,
+                _ => return Ok("".to_owned()),
+            }
+            Ok("OK".to_owned())
+        }
+
+        /// Answers one packet. None means the reply comes later, when the core stops.
+        fn handle(
+            &mut self,
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            packet: &str,
+        ) -> Result<Option<String>, Box<dyn Error>> {
+            let timeout = Duration::from_millis(m_config::CORE_HALT_TIMEOUT_MS);
+            let args = packet.get(1..).unwrap_or("");
+            let reply = match packet.chars().next().unwrap_or(' ') {
+                '?' => {
+                    h.halt_core(core_idx, timeout)?;
+                    "S05".to_owned()
+                }
+                'g' => {
+                    let (core_type, snapshot) = Self::read_registers(h, core_idx)?;
+                    let bits = Self::register_bits(&snapshot, core_type);
+                    gdb_registers(core_type)
+                        .ok_or("unsupported core")?
+                        .iter()
+                        .map(|(_, id)| Self::register_hex(&snapshot, *id, bits))
+                        .collect()
+                }
+                'G' => {
+                    let (core_type, snapshot) = Self::read_registers(h, core_idx)?;
+                    let digits = Self::register_bits(&snapshot, core_type) / 4;
+                    let regs = gdb_registers(core_type).ok_or("unsupported core")?;
+                    for (i, (_, id)) in regs.iter().enumerate() {
+                        if let Some(hex) = args.get(i * digits..(i + 1) * digits) {
+                            if !hex.starts_with('x') {
+                                Self::write_register(h, core_idx, &snapshot, *id, hex)?;
+                            }
+                        }
+                    }
+                    "OK".to_owned()
+                }
+                'p' => {
+                    let (core_type, snapshot) = Self::read_registers(h, core_idx)?;
+                    let bits = Self::register_bits(&snapshot, core_type);
+                    let n = parse_hex(args).ok_or("bad register number")? as usize;
+                    match gdb_registers(core_type).and_then(|r| r.get(n).cloned()) {
+                        Some((_, id)) => Self::register_hex(&snapshot, id, bits),
+                        None => "E01".to_owned(),
+                    }
+                }
+                'P' => {
+                    let (n, hex) = args.split_once('=').ok_or("bad packet")?;
+                    let (core_type, snapshot) = Self::read_registers(h, core_idx)?;
+                    let n = parse_hex(n).ok_or("bad register number")? as usize;
+                    let (_, id) = gdb_registers(core_type)
+                        .and_then(|r| r.get(n).cloned())
+                        .ok_or("unknown register")?;
+                    Self::write_register(h, core_idx, &snapshot, id, hex)?;
+                    "OK".to_owned()
+                }
+                'm' => {
+                    let (addr, len) = args.split_once(',').ok_or("bad packet")?;
+                    let addr = parse_hex(addr).ok_or("bad address")?;
+                    let len = parse_hex(len).ok_or("bad length")? as usize;
+                    let mut buf = vec![0u8; len.min(m_config::GDB_MAX_READ)];
+                    h.read_memory(core_idx, addr, 1, &mut buf)?;
+                    to_hex(&buf)
+                }
+                'M' => {
+                    let (range, data) = args.split_once(':').ok_or("bad packet")?;
+                    let (addr, _) = range.split_once(',').ok_or("bad packet")?;
+                    let addr = parse_hex(addr).ok_or("bad address")?;
+                    let data = from_hex(data).ok_or("bad data")?;
+                    h.write_memory(core_idx, addr, &data)?;
+                    "OK".to_owned()
+                }
+                'c' => {
+                    h.run_core(core_idx)?;
+                    self.b_running = true;
+                    return Ok(None);
+                }
+                's' => {
+                    h.step_core(core_idx)?;
+                    "S05".to_owned()
+                }
+                'Z' | 'z' => {
+                    let mut parts = args.split(',');
+                    let kind = parts.next().unwrap_or("");
+                    let addr = parts.next().and_then(parse_hex).ok_or("bad address")?;
+                    // Flash can't take software breakpoints, both kinds use the comparators.
+                    if kind != "0" && kind != "1" {
+                        "".to_owned()
+                    } else if packet.starts_with('Z') {
+                        h.set_breakpoint(core_idx, addr)?;
+                        self.breakpoints.push(addr);
+                        "OK".to_owned()
+                    } else {
+                        h.clear_breakpoint(core_idx, addr)?;
+                        self.breakpoints.retain(|b| *b != addr);
+                        "OK".to_owned()
+                    }
+                }
+                'D' => {
+                    self.clear_breakpoints(h, core_idx);
+                    h.run_core(core_idx)?;
+                    "OK".to_owned()
+                }
+                'k' => {
+                    self.clear_breakpoints(h, core_idx);
+                    return Ok(None);
+                }
+                'H' | 'T' => "OK".to_owned(),
+                'q' => {
+                    if let Some(hex) = args.strip_prefix("Rcmd,") {
+                        let command = from_hex(hex).ok_or("bad command")?;
+                        Self::monitor(h, core_idx, &String::from_utf8_lossy(&command))?
+                    } else if let Some(range) = args.strip_prefix("Xfer:features:read:target.xml:")
+                    {
+                        let (off, len) = range.split_once(',').ok_or("bad packet")?;
+                        let off = parse_hex(off).ok_or("bad offset")? as usize;
+                        let len = parse_hex(len).ok_or("bad length")? as usize;
+                        let (core_type, snapshot) = Self::read_registers(h, core_idx)?;
+                        let bits = Self::register_bits(&snapshot, core_type);
+                        let xml = target_xml(core_type, bits).ok_or("unsupported core")?;
+                        let end = off.saturating_add(len);
+                        let chunk = xml.get(off.min(xml.len())..end.min(xml.len()));
+                        match chunk {
+                            Some(c) if end >= xml.len() => format!("l{}", c),
+                            Some(c) => format!("m{}", c),
+                            None => "E01".to_owned(),
+                        }
+                    } else if args.starts_with("Supported") {
+                        "PacketSize=4000;qXfer:features:read+".to_owned()
+                    } else {
+                        match args {
+                            "Attached" => "1".to_owned(),
+                            "C" => "QC1".to_owned(),
+                            "fThreadInfo" => "m1".to_owned(),
+                            "sThreadInfo" => "l".to_owned(),
+                            _ => "".to_owned(),
+                        }
+                    }
+                }
+                _ => "".to_owned(),
+            };
+            Ok(Some(reply))
+        }
+
+        /// Answers the client's requests. Returns true while the server is up, so the
+        /// caller keeps repainting.
+        pub fn poll(&mut self, h: Option<&mut ProbeRsHandler>, core_idx: usize) -> bool {
+            let Some(events) = self.events.as_ref() else {
+                return false;
+            };
+            let mut received = Vec::new();
+            loop {
+                match events.try_recv() {
+                    Ok(e) => received.push(e),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        self.rst_info = Some("gdb server stopped".to_owned());
+                        self.stop_server();
+                        return false;
+                    }
+                }
+            }
+
+            let Some(h) = h else {
+                // Nothing to debug without a session.
+                for e in received {
+                    if let GdbEvent::Packet(_) = e {
+                        self.reply("E01");
+                    }
+                }
+                return true;
+            };
+            for e in received {
+                match e {
+                    GdbEvent::Connected(peer) => {
+                        self.peer = Some(peer);
+                        self.b_running = false;
+                    }
+                    GdbEvent::Disconnected => {
+                        self.peer = None;
+                        self.b_running = false;
+                        self.clear_breakpoints(h, core_idx);
+                    }
+                    GdbEvent::Interrupt => {
+                        let timeout = Duration::from_millis(m_config::CORE_HALT_TIMEOUT_MS);
+                        if let Err(e) = h.halt_core(core_idx, timeout) {
+                            self.rst_info = Some(format!("{}", e));
+                        }
+                    }
+                    GdbEvent::Packet(p) => match self.handle(h, core_idx, &p) {
+                        Ok(Some(r)) => self.reply(&r),
+                        Ok(None) => {}
+                        Err(e) => {
+                            self.rst_info = Some(format!("{}: {}", p, e));
+                            self.reply("E01");
+                        }
+                    },
+                }
+            }
+
+            if self.b_running {
+                if let Ok(Some(CoreStatus::Halted(_))) = h.core_status(core_idx) {
+                    self.b_running = false;
+                    self.reply("S05");
+                }
+            }
+            true
+        }
+
+        pub fn ui(&mut self, ui: &mut eframe::egui::Ui) {
+            if self.port == 0 {
+                self.port = m_config::GDB_DEFAULT_PORT;
+            }
+            ui.horizontal(|ui| {
+                ui.label("GDB server");
+                ui.separator();
+                let b_started = self.thread.is_some();
+                ui.add_enabled(
+                    !b_started,
+                    eframe::egui::DragValue::new(&mut self.port).prefix("port "),
+                );
+                if !b_started {
+                    if ui.button("start").clicked() {
+                        match self.start_server() {
+                            Ok(_) => {
+                                self.rst_info.take();
+                            }
+                            Err(e) => {
+                                self.rst_info = Some(format!("{}", e));
+                            }
+                        }
+                    }
+                    ui.label("stopped");
+                } else {
+                    if ui.button("stop").clicked() {
+                        self.stop_server();
+                    }
+                    match self.peer {
+                        Some(peer) => {
+                            let state = if self.b_running { "running" } else { "halted" };
+                            ui.label(format!("client {} connected, target {}", peer, state));
+                        }
+                        None => {
+                            ui.label(format!(
+                                "listening on 127.0.0.1:{}, `target remote :{}`",
+                                self.port, self.port
+                            ));
+                        }
+                    }
+                }
+                if let Some(info) = self.rst_info.as_ref() {
+                    ui.label(info);
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,167 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_itm {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum ExceptionAction {
+        Entered,
+        Exited,
+        Returned,
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum ItmPacket {
+        /// Data written to an ITM stimulus port.
+        Instrumentation { port: u8, payload: Vec<u8> },
+        /// A periodic PC sample, None when the core was sleeping.
+        PcSample(Option<u32>),
+        Exception {
+            number: u16,
+            action: ExceptionAction,
+        },
+        /// The ITM dropped packets.
+        Overflow,
+    }
+
+    const DWT_ID_EXCEPTION: u8 = 1;
+    const DWT_ID_PC_SAMPLE: u8 = 2;
+
+    /// Decoder for the ITM/DWT packet protocol, see the ARMv7-M Architecture
+    /// Reference Manual, appendix D4.
+    #[derive(Default)]
+    pub struct ItmDecoder {
+        /// Bytes of a packet that isn't complete yet.
+        pending: Vec<u8>,
+        b_in_sync: bool,
+        /// Bytes that didn't form a valid packet.
+        pub n_malformed: u64,
+    }
+
+    /// Length of a packet made of a header and continuation bytes (bit 7 set when
+    /// another byte follows), None while it is incomplete.
+    fn continued_len(data: &[u8], max_payload: usize) -> Option<usize> {
+        for (i, b) in data.iter().enumerate().skip(1).take(max_payload) {
+            if b & 0x80 == 0 || i == max_payload {
+                return Some(i + 1);
+            }
+        }
+        None
+    }
+
+    impl ItmDecoder {
+        /// Decodes the packets in `data`, keeping a trailing partial packet for the
+        /// next call.
+        pub fn feed(&mut self, data: &[u8]) -> Vec<ItmPacket> {
+            self.pending.extend_from_slice(data);
+            let mut packets = Vec::new();
+            let mut pos = 0;
+            while pos < self.pending.len() {
+                let buf = &self.pending[pos..];
+                let header = buf[0];
+
+                // Synchronization: at least 47 zero bits followed by a set bit.
+                if header == 0x00 {
+                    self.b_in_sync = true;
+                    pos += 1;
+                    continue;
+                }
+                if header == 0x80 && self.b_in_sync {
+                    self.b_in_sync = false;
+                    pos += 1;
+                    continue;
+                }
+                self.b_in_sync = false;
+
+                let len = if header == 0x70 {
+                    packets.push(ItmPacket::Overflow);
+                    Some(1)
+                } else if header & 0x03 != 0 {
+                    let size = match header & 0x03 {
+                        1 => 1,
+                        2 => 2,
+                        _ => 4,
+                    };
+                    if buf.len() < size + 1 {
+                        None
+                    } else {
+                        let payload = &buf[1..=size];
+                        let id = header >> 3;
+                        if header & 0x04 == 0 {
+                            packets.push(ItmPacket::Instrumentation {
+                                port: id,
+                                payload: payload.to_vec(),
+                            });
+                        } else if let Some(p) = Self::hardware_packet(id, payload) {
+                            packets.push(p);
+                        }
+                        Some(size + 1)
+                    }
+                } else if header & 0x0F == 0 {
+                    // Local timestamp, either one byte or with continuation bytes.
+                    if header & 0x80 == 0 {
+                        Some(1)
+                    } else {
+                        continued_len(buf, 4)
+                    }
+                } else if header == 0x94 {
+                    continued_len(buf, 4)
+                } else if header == 0xB4 {
+                    continued_len(buf, 6)
+                } else if header & 0x0B == 0x08 {
+                    // Extension packet.
+                    if header & 0x80 == 0 {
+                        Some(1)
+                    } else {
+                        continued_len(buf, 4)
+                    }
+                } else {
+                    self.n_malformed += 1;
+                    Some(1)
+                };
+
+                match len {
+                    Some(n) => pos += n,
+                    None => break,
+                }
+            }
+            self.pending.drain(..pos);
+            packets
+        }
+
+        fn hardware_packet(id: u8, payload: &[u8]) -> Option<ItmPacket> {
+            match (id, payload.len()) {
+                (DWT_ID_EXCEPTION, 2) => {
+                    let number = u16::from_le_bytes([payload[0], payload[1]]) & 0x1FF;
+                    let action = match (payload[1] >> 4) & 0x03 {
+                        1 => ExceptionAction::Entered,
+                        2 => ExceptionAction::Exited,
+                        3 => ExceptionAction::Returned,
+                        _ => return None,
+                    };
+                    Some(ItmPacket::Exception { number, action })
+                }
+                (DWT_ID_PC_SAMPLE, 4) => Some(ItmPacket::PcSample(Some(u32::from_le_bytes([
+                    payload[0], payload[1], payload[2], payload[3],
+                ])))),
+                (DWT_ID_PC_SAMPLE, 1) => Some(ItmPacket::PcSample(None)),
+                // Event counters and data trace aren't decoded.
+                _ => None,
+            }
+        }
+    }
+}
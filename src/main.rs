@@ -19,16 +19,42 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 #![warn(clippy::all)]
 
+mod breakpoint_opts;
+mod call_stack_opts;
+mod chip_info_opts;
 mod configuration;
+mod core_dump_opts;
+mod custom_targets;
+mod debug_opts;
+mod disasm_opts;
+mod dwarf_info;
+mod elf_symbols;
+mod fault_opts;
 mod flash_opts;
+mod gdb_server;
+mod itm;
+mod memory_opts;
+mod peripheral_opts;
 mod probe_opts;
 mod probe_rs_invoke;
+mod profiler_opts;
+mod register_opts;
+mod riscv_disasm;
 mod rtt_opts;
+mod rtt_trigger;
+mod semihosting_opts;
+mod svd;
+mod trace_opts;
+mod watch_opts;
+mod watchpoint_opts;
 
 use configuration::m_config;
+use custom_targets::m_custom_targets;
+use debug_opts::m_debug_opts::CoreDebug;
 use flash_opts::m_flash_opts::FlashProgram;
 use probe_opts::m_probe_opts::ProbeOperations;
 use rtt_opts::m_rtt_opts::Rttio;
+use trace_opts::m_trace_opts::SwoTrace;
 
 fn main() -> eframe::Result {
     let options = eframe::NativeOptions {
@@ -39,7 +65,12 @@ fn main() -> eframe::Result {
     eframe::run_native(
         m_config::APP_NAME,
         options,
-        Box::new(|_| Ok(Box::<MyApp>::default())),
+        Box::new(|_| {
+            Ok(Box::new(MyApp {
+                flash_opts: FlashProgram::new(m_custom_targets::load_saved()),
+                ..Default::default()
+            }))
+        }),
     )
 }
 
@@ -48,10 +79,18 @@ struct MyApp {
     stack_window: ProbeOperations,
     flash_opts: FlashProgram,
     rttio_opts: Rttio,
+    debug_opts: CoreDebug,
+    trace_opts: SwoTrace,
 }
 
 impl eframe::App for MyApp {
     fn update(&mut self, ctx: &eframe::egui::Context, _frame: &mut eframe::Frame) {
+        self.rttio_opts.poll_gdb(ctx);
+        self.rttio_opts.poll(ctx);
+        self.trace_opts.poll(ctx);
+        if self.debug_opts.poll(ctx) {
+            self.stack_window = ProbeOperations::Debug;
+        }
         eframe::egui::CentralPanel::default().show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.selectable_value(
@@ -64,6 +103,16 @@ impl eframe::App for MyApp {
                     ProbeOperations::RttIO,
                     m_config::PAGE_2_LABEL,
                 );
+                ui.selectable_value(
+                    &mut self.stack_window,
+                    ProbeOperations::Debug,
+                    m_config::PAGE_3_LABEL,
+                );
+                ui.selectable_value(
+                    &mut self.stack_window,
+                    ProbeOperations::Trace,
+                    m_config::PAGE_4_LABEL,
+                );
             });
             ui.separator();
             match self.stack_window {
@@ -73,6 +122,12 @@ impl eframe::App for MyApp {
                 ProbeOperations::RttIO => {
                     self.rttio_opts.ui(ctx, ui);
                 }
+                ProbeOperations::Debug => {
+                    self.debug_opts.ui(ctx, ui);
+                }
+                ProbeOperations::Trace => {
+                    self.trace_opts.ui(ctx, ui);
+                }
             }
         });
     }
@@ -0,0 +1,277 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_memory_opts {
+    use crate::configuration::m_config;
+    use crate::debug_opts::m_debug_opts::parse_number;
+    use crate::elf_symbols::m_elf_symbols::ElfSymbols;
+    use crate::probe_rs_invoke::probe_rs_integration::ProbeRsHandler;
+    use std::time::{Duration, Instant};
+
+    const BYTES_PER_ROW: usize = 16;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    enum WordSize {
+        #[default]
+        U8,
+        U16,
+        U32,
+    }
+
+    impl WordSize {
+        fn bytes(&self) -> usize {
+            match self {
+                WordSize::U8 => 1,
+                WordSize::U16 => 2,
+                WordSize::U32 => 4,
+            }
+        }
+    }
+
+    /// Formats one little-endian word as zero-padded hex.
+    fn format_word(word: &[u8]) -> String {
+        let mut bytes = [0u8; 4];
+        bytes[..word.len()].copy_from_slice(word);
+        format!(
+            "{:0width$x}",
+            u32::from_le_bytes(bytes),
+            width = word.len() * 2
+        )
+    }
+
+    #[derive(Default)]
+    pub struct MemoryView {
+        address_s: String,
+        base_address: Option<u64>,
+        length: usize,
+        word_size: WordSize,
+        data: Vec<u8>,
+        prev_data: Vec<u8>,
+        b_auto_refresh: bool,
+        refresh_interval_ms: u64,
+        last_refresh: Option<Instant>,
+        editing: Option<(usize, String)>,
+        rst_info: Option<String>,
+    }
+
+    impl MemoryView {
+        fn resolve_address(&mut self, symbols: &ElfSymbols) {
+            let address = match parse_number(&self.address_s) {
+                Some(a) => u64::try_from(a).ok(),
+                None => symbols.lookup(&self.address_s).map(|s| s.address),
+            };
+            match address {
+                Some(a) => {
+                    // Keep every access naturally aligned, peripherals care about that.
+                    self.base_address = Some(a & !(m_config::MEMORY_VIEW_ALIGN - 1));
+                    self.data.clear();
+                    self.prev_data.clear();
+                    self.rst_info.take();
+                }
+                None => {
+                    self.rst_info = Some(format!("unknown address or symbol: {}", self.address_s));
+                }
+            }
+        }
+
+        fn refresh(&mut self, h: &mut ProbeRsHandler, core_idx: usize) {
+            self.last_refresh = Some(Instant::now());
+            let Some(address) = self.base_address else {
+                return;
+            };
+            let mut buf = vec![0u8; self.length];
+            match h.read_memory(core_idx, address, self.word_size.bytes(), &mut buf) {
+                Ok(_) => {
+                    if self.data.len() == buf.len() {
+                        self.prev_data = std::mem::replace(&mut self.data, buf);
+                    } else {
+                        self.data = buf;
+                        self.prev_data.clear();
+                    }
+                    self.rst_info.take();
+                }
+                Err(e) => {
+                    self.rst_info = Some(format!("{}", e));
+                }
+            }
+        }
+
+        pub fn ui(
+            &mut self,
+            ui: &mut eframe::egui::Ui,
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            symbols: &ElfSymbols,
+        ) {
+            if self.length == 0 {
+                self.length = m_config::MEMORY_VIEW_LENGTH;
+            }
+            if self.refresh_interval_ms == 0 {
+                self.refresh_interval_ms = m_config::MEMORY_VIEW_REFRESH_MS;
+            }
+
+            ui.horizontal(|ui| {
+                let resp = ui.add(
+                    eframe::egui::TextEdit::singleline(&mut self.address_s)
+                        .hint_text("address or symbol")
+                        .desired_width(200.0),
+                );
+                let b_enter =
+                    resp.lost_focus() && ui.input(|i| i.key_pressed(eframe::egui::Key::Enter));
+                if ui.button("go").clicked() || b_enter {
+                    self.resolve_address(symbols);
+                    self.refresh(h, core_idx);
+                }
+                let old_word_size = self.word_size;
+                eframe::egui::ComboBox::from_label("word")
+                    .selected_text(format!("{}-bit", self.word_size.bytes() * 8))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.word_size, WordSize::U8, "8-bit");
+                        ui.selectable_value(&mut self.word_size, WordSize::U16, "16-bit");
+                        ui.selectable_value(&mut self.word_size, WordSize::U32, "32-bit");
+                    });
+                let old_length = self.length;
+                ui.add(
+                    eframe::egui::Slider::new(&mut self.length, BYTES_PER_ROW..=4096)
+                        .step_by(BYTES_PER_ROW as f64)
+                        .text("bytes"),
+                );
+                if old_word_size != self.word_size || old_length != self.length {
+                    self.refresh(h, core_idx);
+                }
+                if ui.button("refresh").clicked() {
+                    self.refresh(h, core_idx);
+                }
+                ui.checkbox(&mut self.b_auto_refresh, "auto refresh");
+                ui.add_enabled(
+                    self.b_auto_refresh,
+                    eframe::egui::Slider::new(&mut self.refresh_interval_ms, 50..=5000)
+                        .logarithmic(true)
+                        .text("interval (ms)"),
+                );
+            });
+
+            let refresh_interval = Duration::from_millis(self.refresh_interval_ms);
+            if self.b_auto_refresh && self.editing.is_none() {
+                if self
+                    .last_refresh
+                    .is_none_or(|t| t.elapsed() >= refresh_interval)
+                {
+                    self.refresh(h, core_idx);
+                }
+                ui.ctx().request_repaint_after(refresh_interval);
+            }
+
+            if let Some(info) = self.rst_info.as_ref() {
+                ui.label(info);
+            }
+
+            let Some(base_address) = self.base_address else {
+                return;
+            };
+            if let Some((s, offset)) = symbols.symbol_at(base_address) {
+                ui.label(format!("{:#010x} = {}+{:#x}", base_address, s.name, offset));
+            }
+
+            let word_bytes = self.word_size.bytes();
+            let mut write_req = None;
+            let mut start_edit = None;
+            eframe::egui::ScrollArea::vertical()
+                .auto_shrink(false)
+                .show(ui, |ui| {
+                    for (row, chunk) in self.data.chunks(BYTES_PER_ROW).enumerate() {
+                        let row_offset = row * BYTES_PER_ROW;
+                        ui.horizontal(|ui| {
+                            ui.monospace(format!("{:08x}:", base_address + row_offset as u64));
+                            for w in (0..chunk.len()).step_by(word_bytes) {
+                                let offset = row_offset + w;
+                                if let Some((edit_offset, text)) = self.editing.as_mut() {
+                                    if *edit_offset == offset {
+                                        let resp = ui.add(
+                                            eframe::egui::TextEdit::singleline(text)
+                                                .desired_width(word_bytes as f32 * 18.0),
+                                        );
+                                        if resp.lost_focus() {
+                                            if ui.input(|i| i.key_pressed(eframe::egui::Key::Enter))
+                                            {
+                                                write_req = Some((offset, text.clone()));
+                                            }
+                                            self.editing = None;
+                                        } else {
+                                            resp.request_focus();
+                                        }
+                                        continue;
+                                    }
+                                }
+                                let b_changed = self.prev_data.len() == self.data.len()
+                                    && self.prev_data[offset..offset + word_bytes]
+                                        != self.data[offset..offset + word_bytes];
+                                let mut text = eframe::egui::RichText::new(format_word(
+                                    &self.data[offset..offset + word_bytes],
+                                ))
+                                .monospace();
+                                if b_changed {
+                                    text = text.color(ui.visuals().warn_fg_color);
+                                }
+                                let resp = ui
+                                    .add(
+                                        eframe::egui::Label::new(text)
+                                            .sense(eframe::egui::Sense::click()),
+                                    )
+                                    .on_hover_text(format!(
+                                        "{:#010x}, double click to edit",
+                                        base_address + offset as u64
+                                    ));
+                                if resp.double_clicked() {
+                                    start_edit = Some(offset);
+                                }
+                            }
+                            let ascii: String = chunk
+                                .iter()
+                                .map(|b| {
+                                    if b.is_ascii_graphic() || *b == b' ' {
+                                        *b as char
+                                    } else {
+                                        '.'
+                                    }
+                                })
+                                .collect();
+                            ui.monospace(format!(" {}", ascii));
+                        });
+                    }
+                });
+
+            if let Some(offset) = start_edit {
+                self.editing = Some((
+                    offset,
+                    format!("0x{}", format_word(&self.data[offset..offset + word_bytes])),
+                ));
+            }
+            if let Some((offset, text)) = write_req {
+                let address = base_address + offset as u64;
+                match parse_number(&text).and_then(|v| u32::try_from(v).ok()) {
+                    Some(v) => match h.write_memory_word(core_idx, address, word_bytes, v) {
+                        Ok(_) => self.refresh(h, core_idx),
+                        Err(e) => self.rst_info = Some(format!("{}", e)),
+                    },
+                    None => self.rst_info = Some(format!("invalid value: {}", text)),
+                }
+            }
+        }
+    }
+}
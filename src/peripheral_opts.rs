@@ -0,0 +1,425 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_peripheral_opts {
+    use crate::configuration::m_config;
+    use crate::debug_opts::m_debug_opts::parse_number;
+    use crate::probe_rs_invoke::probe_rs_integration::ProbeRsHandler;
+    use crate::svd::m_svd::{Register, SvdDevice};
+    use egui_file::FileDialog;
+    use probe_rs::CoreStatus;
+    use std::{
+        collections::HashMap,
+        path::PathBuf,
+        time::{Duration, Instant},
+    };
+
+    fn format_value(value: Option<u64>, bits: u32) -> String {
+        match value {
+            Some(v) => format!("{:#0width$x}", v, width = bits.div_ceil(4) as usize + 2),
+            None => "?".to_owned(),
+        }
+    }
+
+    #[derive(Default)]
+    pub struct PeripheralView {
+        file_dialog: Option<FileDialog>,
+        selected_file: Option<PathBuf>,
+        device: SvdDevice,
+        filter_s: String,
+        /// Register values by address, None when the read failed.
+        values: HashMap<u64, Option<u64>>,
+        prev_values: HashMap<u64, Option<u64>>,
+        /// Peripherals expanded in the tree, only those are read.
+        shown: Vec<usize>,
+        b_auto_refresh: bool,
+        refresh_interval_ms: u64,
+        last_refresh: Option<Instant>,
+        b_was_halted: bool,
+        editing: Option<(u64, String)>,
+        rst_info: Option<String>,
+    }
+
+    impl PeripheralView {
+        fn load_svd(&mut self) {
+            let Some(f) = self.selected_file.as_ref() else {
+                return;
+            };
+            match SvdDevice::load(f) {
+                Ok(device) => {
+                    self.rst_info = device
+                        .is_empty()
+                        .then(|| "no peripherals in the SVD file".to_owned());
+                    self.device = device;
+                }
+                Err(e) => {
+                    self.device = SvdDevice::default();
+                    self.rst_info = Some(format!("{}", e));
+                }
+            }
+            self.values.clear();
+            self.prev_values.clear();
+            self.shown.clear();
+        }
+
+        fn read_register(h: &mut ProbeRsHandler, core_idx: usize, r: &Register) -> Option<u64> {
+            let size = (r.size as usize).div_ceil(8).clamp(1, 8);
+            let mut buf = vec![0u8; size];
+            // Natural width accesses, some peripherals fault on narrower ones.
+            let word_size = if size >= 4 { 4 } else { size };
+            h.read_memory(core_idx, r.address, word_size, &mut buf)
+                .ok()?;
+            let mut bytes = [0u8; 8];
+            bytes[..size].copy_from_slice(&buf);
+            Some(u64::from_le_bytes(bytes))
+        }
+
+        fn refresh(&mut self, h: &mut ProbeRsHandler, core_idx: usize, peripherals: &[usize]) {
+            self.last_refresh = Some(Instant::now());
+            for p in peripherals {
+                let Some(p) = self.device.peripherals.get(*p) else {
+                    continue;
+                };
+                // Write-only registers may have side effects on read, leave them alone.
+                for r in p.registers.iter().filter(|r| r.access.is_readable()) {
+                    let value = Self::read_register(h, core_idx, r);
+                    if let Some(old) = self.values.insert(r.address, value) {
+                        self.prev_values.insert(r.address, old);
+                    }
+                }
+            }
+        }
+
+        fn write(&mut self, h: &mut ProbeRsHandler, core_idx: usize, r: &Register, value: u64) {
+            let size = (r.size as usize).div_ceil(8);
+            let rst = u32::try_from(value)
+                .map_err(|e| e.into())
+                .and_then(|v| h.write_memory_word(core_idx, r.address, size, v));
+            match rst {
+                Ok(_) => {
+                    self.rst_info.take();
+                }
+                Err(e) => {
+                    self.rst_info = Some(format!("{}", e));
+                }
+            }
+            let value = if r.access.is_readable() {
+                Self::read_register(h, core_idx, r)
+            } else {
+                Some(value)
+            };
+            self.values.insert(r.address, value);
+        }
+
+        pub fn ui(
+            &mut self,
+            ui: &mut eframe::egui::Ui,
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            status: Option<CoreStatus>,
+        ) {
+            if self.refresh_interval_ms == 0 {
+                self.refresh_interval_ms = m_config::SVD_REFRESH_MS;
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button("Select svd file").clicked() {
+                    let mut dialog = FileDialog::open_file(self.selected_file.clone());
+                    dialog.open();
+                    self.file_dialog = Some(dialog);
+                }
+                ui.label(format!("Selected svd file: {:?}", self.selected_file));
+                if !self.device.is_empty() {
+                    ui.label(format!(
+                        "{} ({} peripherals)",
+                        self.device.name,
+                        self.device.peripherals.len()
+                    ));
+                }
+            });
+            let mut b_svd_selected = false;
+            if let Some(dialog) = &mut self.file_dialog {
+                if dialog.show(ui.ctx()).selected() {
+                    if let Some(file) = dialog.path() {
+                        self.selected_file = Some(file.to_path_buf());
+                        b_svd_selected = true;
+                    }
+                }
+            }
+            if b_svd_selected {
+                self.load_svd();
+            }
+
+            let mut b_refresh = false;
+            ui.horizontal(|ui| {
+                ui.add(
+                    eframe::egui::TextEdit::singleline(&mut self.filter_s)
+                        .hint_text("peripherals filter")
+                        .desired_width(150.0),
+                );
+                if ui.button("refresh").clicked() {
+                    b_refresh = true;
+                }
+                ui.checkbox(&mut self.b_auto_refresh, "auto refresh");
+                ui.add_enabled(
+                    self.b_auto_refresh,
+                    eframe::egui::Slider::new(&mut self.refresh_interval_ms, 50..=5000)
+                        .logarithmic(true)
+                        .text("interval (ms)"),
+                );
+            });
+            if let Some(info) = self.rst_info.as_ref() {
+                ui.label(info);
+            }
+
+            let b_halted = status.is_some_and(|s| s.is_halted());
+            if b_halted && !self.b_was_halted {
+                b_refresh = true;
+            }
+            self.b_was_halted = b_halted;
+            let refresh_interval = Duration::from_millis(self.refresh_interval_ms);
+            if self.b_auto_refresh && self.editing.is_none() {
+                if self
+                    .last_refresh
+                    .is_none_or(|t| t.elapsed() >= refresh_interval)
+                {
+                    b_refresh = true;
+                }
+                ui.ctx().request_repaint_after(refresh_interval);
+            }
+            if b_refresh {
+                let shown = self.shown.clone();
+                self.refresh(h, core_idx, &shown);
+            }
+
+            let filter = self.filter_s.to_ascii_lowercase();
+            let mut shown = Vec::new();
+            let mut write_req = None;
+            let mut start_edit = None;
+            eframe::egui::ScrollArea::vertical()
+                .auto_shrink(false)
+                .show(ui, |ui| {
+                    for (pi, p) in self.device.peripherals.iter().enumerate() {
+                        if !filter.is_empty() && !p.name.to_ascii_lowercase().contains(&filter) {
+                            continue;
+                        }
+                        eframe::egui::CollapsingHeader::new(format!(
+                            "{} @ {:#010x}",
+                            p.name, p.base_address
+                        ))
+                        .id_salt(("svd_peripheral", pi))
+                        .show(ui, |ui| {
+                            shown.push(pi);
+                            for (ri, r) in p.registers.iter().enumerate() {
+                                let value = self.values.get(&r.address).copied().flatten();
+                                let b_changed = self
+                                    .prev_values
+                                    .get(&r.address)
+                                    .is_some_and(|old| *old != value);
+                                let mut title = eframe::egui::RichText::new(format!(
+                                    "{} @ {:#010x} = {}",
+                                    r.name,
+                                    r.address,
+                                    format_value(value, r.size)
+                                ))
+                                .monospace();
+                                if b_changed {
+                                    title = title.color(ui.visuals().warn_fg_color);
+                                }
+                                eframe::egui::CollapsingHeader::new(title)
+                                    .id_salt(("svd_register", pi, ri))
+                                    .show(ui, |ui| {
+                                        if !r.description.is_empty() {
+                                            ui.weak(&r.description);
+                                        }
+                                        ui.horizontal(|ui| {
+                                            ui.label(format!(
+                                                "{}-bit, reset {}",
+                                                r.size,
+                                                format_value(Some(r.reset_value), r.size)
+                                            ));
+                                            if !r.access.is_writable() {
+                                                ui.label("read-only");
+                                                return;
+                                            }
+                                            match self.editing.as_mut() {
+                                                Some((a, text)) if *a == r.address => {
+                                                    let resp = ui.add(
+                                                        eframe::egui::TextEdit::singleline(text)
+                                                            .desired_width(100.0),
+                                                    );
+                                                    if resp.lost_focus() {
+                                                        if ui.input(|i| {
+                                                            i.key_pressed(eframe::egui::Key::Enter)
+                                                        }) {
+                                                            match parse_number(text) {
+                                                                Some(v) => {
+                                                                    write_req =
+                                                                        Some((pi, ri, v as u64))
+                                                                }
+                                                                None => {
+                                                                    self.rst_info = Some(format!(
+                                                                        "invalid value: {}",
+                                                                        text
+                                                                    ))
+                                                                }
+                                                            }
+                                                        }
+                                                        self.editing = None;
+                                                    } else {
+                                                        resp.request_focus();
+                                                    }
+                                                }
+                                                _ => {
+                                                    if ui.button("edit").clicked() {
+                                                        start_edit = Some((r.address, value));
+                                                    }
+                                                }
+                                            }
+                                        });
+
+                                        eframe::egui::Grid::new(("svd_fields", pi, ri))
+                                            .striped(true)
+                                            .show(ui, |ui| {
+                                                for f in r.fields.iter() {
+                                                    let bits = if f.bit_width == 1 {
+                                                        format!("[{}]", f.bit_offset)
+                                                    } else {
+                                                        format!(
+                                                            "[{}:{}]",
+                                                            f.bit_offset + f.bit_width - 1,
+                                                            f.bit_offset
+                                                        )
+                                                    };
+                                                    let name = ui.label(&f.name);
+                                                    if !f.description.is_empty() {
+                                                        name.on_hover_text(&f.description);
+                                                    }
+                                                    ui.monospace(bits);
+                                                    let Some(v) = value else {
+                                                        ui.label("?");
+                                                        ui.end_row();
+                                                        continue;
+                                                    };
+                                                    let fv = f.extract(v);
+                                                    let b_writable = r.access.is_writable()
+                                                        && f.access.is_writable();
+                                                    if !f.values.is_empty() {
+                                                        let text = match f.value_name(fv) {
+                                                            Some(n) => format!("{} ({:#x})", n, fv),
+                                                            None => format!("{:#x}", fv),
+                                                        };
+                                                        ui.add_enabled_ui(b_writable, |ui| {
+                                                            eframe::egui::ComboBox::from_id_salt((
+                                                                "svd_enum", pi, ri, &f.name,
+                                                            ))
+                                                            .selected_text(text)
+                                                            .show_ui(ui, |ui| {
+                                                                for e in f.values.iter() {
+                                                                    let resp = ui
+                                                                        .selectable_label(
+                                                                            e.value == fv,
+                                                                            format!(
+                                                                                "{} ({:#x})",
+                                                                                e.name, e.value
+                                                                            ),
+                                                                        )
+                                                                        .on_hover_text(
+                                                                            &e.description,
+                                                                        );
+                                                                    if resp.clicked() {
+                                                                        write_req = Some((
+                                                                            pi,
+                                                                            ri,
+                                                                            f.insert(v, e.value),
+                                                                        ));
+                                                                    }
+                                                                }
+                                                            });
+                                                        });
+                                                    } else if f.bit_width == 1 {
+                                                        let mut b = fv != 0;
+                                                        if ui
+                                                            .add_enabled(
+                                                                b_writable,
+                                                                eframe::egui::Checkbox::without_text(
+                                                                    &mut b,
+                                                                ),
+                                                            )
+                                                            .changed()
+                                                        {
+                                                            write_req =
+                                                                Some((pi, ri, f.insert(v, b as u64)));
+                                                        }
+                                                    } else {
+                                                        let mut n = fv;
+                                                        let max = f.mask() >> f.bit_offset;
+                                                        let resp = ui.add_enabled(
+                                                            b_writable,
+                                                            eframe::egui::DragValue::new(&mut n)
+                                                                .range(0..=max)
+                                                                .hexadecimal(1, false, false),
+                                                        );
+                                                        if resp.drag_stopped()
+                                                            || (resp.lost_focus() && n != fv)
+                                                        {
+                                                            write_req =
+                                                                Some((pi, ri, f.insert(v, n)));
+                                                        }
+                                                    }
+                                                    ui.end_row();
+                                                }
+                                            });
+                                    });
+                            }
+                        })
+                        .header_response
+                        .on_hover_text(&p.description);
+                    }
+                });
+
+            // Read the peripherals that were just expanded.
+            let opened: Vec<usize> = shown
+                .iter()
+                .filter(|p| !self.shown.contains(p))
+                .copied()
+                .collect();
+            self.shown = shown;
+            if !opened.is_empty() {
+                self.refresh(h, core_idx, &opened);
+            }
+            if let Some((address, value)) = start_edit {
+                self.editing = Some((
+                    address,
+                    value.map(|v| format!("{:#x}", v)).unwrap_or_default(),
+                ));
+            }
+            if let Some((pi, ri, value)) = write_req {
+                if let Some(r) = self
+                    .device
+                    .peripherals
+                    .get(pi)
+                    .and_then(|p| p.registers.get(ri))
+                    .cloned()
+                {
+                    self.write(h, core_idx, &r, value);
+                }
+            }
+        }
+    }
+}
@@ -17,15 +17,164 @@
  */
 
 pub mod m_probe_opts {
-    #[derive(Clone, Copy, Debug, PartialEq)]
+    use crate::probe_rs_invoke::probe_rs_integration::ProbeRsHandler;
+    use probe_rs::probe::WireProtocol;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
     pub enum ProbeOperations {
+        #[default]
         FlashProgram,
         RttIO,
+        Debug,
+        Trace,
+    }
+
+    fn protocol_label(protocol: Option<WireProtocol>) -> String {
+        protocol.map_or("auto".to_owned(), |p| format!("{}", p))
     }
 
-    impl Default for ProbeOperations {
-        fn default() -> Self {
-            Self::FlashProgram
+    /// Protocol and speed controls, applied the next time the probe is attached.
+    pub fn probe_config_ui(ui: &mut eframe::egui::Ui, h: &mut ProbeRsHandler) {
+        ui.add_enabled_ui(h.session.is_none(), |ui| {
+            eframe::egui::ComboBox::from_label("protocol")
+                .selected_text(protocol_label(h.protocol))
+                .show_ui(ui, |ui| {
+                    for p in [None, Some(WireProtocol::Swd), Some(WireProtocol::Jtag)] {
+                        ui.selectable_value(&mut h.protocol, p, protocol_label(p));
+                    }
+                });
+            ui.add(
+                eframe::egui::DragValue::new(&mut h.speed_khz)
+                    .range(0..=100_000)
+                    .custom_formatter(|v, _| {
+                        if v == 0.0 {
+                            "default".to_owned()
+                        } else {
+                            format!("{} kHz", v)
+                        }
+                    }),
+            )
+            .on_hover_text("probe clock, 0 for the probe's default");
+        });
+        if h.session.is_some() {
+            if let Some(speed) = h.active_speed_khz {
+                ui.label(format!(
+                    "{} @ {} kHz",
+                    protocol_label(h.active_protocol),
+                    speed
+                ));
+            }
         }
     }
+
+    /// Auto-detect button and the targets proposed from the chip's identification.
+    /// A target probe-rs identified on its own is selected right away.
+    pub fn chip_detection_ui(
+        ui: &mut eframe::egui::Ui,
+        h: &mut ProbeRsHandler,
+        probe_idx: usize,
+        target_chip: &mut String,
+    ) {
+        if ui
+            .add_enabled(
+                h.session.is_none(),
+                eframe::egui::Button::new("auto-detect target"),
+            )
+            .clicked()
+        {
+            if let Some(name) = h.detect_chip(probe_idx).detected.clone() {
+                *target_chip = name;
+            }
+        }
+        let Some(detection) = h.chip_detection.as_ref() else {
+            return;
+        };
+        let mut b_dismiss = false;
+        ui.group(|ui| {
+            for l in detection.identification.iter() {
+                ui.label(l);
+            }
+            if detection.candidates.is_empty() {
+                ui.label("no matching target in the registry");
+            }
+            for c in detection.candidates.iter() {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(format!("{}:", c.family));
+                    for v in c.variants.iter() {
+                        if ui.selectable_label(target_chip == v, v).clicked() {
+                            *target_chip = v.clone();
+                        }
+                    }
+                });
+            }
+            if ui.button("dismiss").clicked() {
+                b_dismiss = true;
+            }
+        });
+        if b_dismiss {
+            h.chip_detection = None;
+        }
+    }
+
+    /// Explains a failed attach to a locked chip and offers to unlock it by mass
+    /// erasing, once the user confirmed. Returns true when the target got attached.
+    pub fn unlock_ui(
+        ui: &mut eframe::egui::Ui,
+        h: &mut ProbeRsHandler,
+        b_confirmed: &mut bool,
+        probe_idx: usize,
+        target_chip: &str,
+    ) -> bool {
+        let Some(message) = h
+            .protection_error
+            .as_ref()
+            .filter(|p| p.matches(probe_idx, target_chip))
+            .map(|p| p.message.clone())
+        else {
+            *b_confirmed = false;
+            return false;
+        };
+        let mut b_attached = false;
+        ui.group(|ui| {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!(
+                    "{} looks read-out protected, the debugger can't access it: {}",
+                    target_chip, message
+                ),
+            );
+            ui.label(
+                "Unlocking mass erases the chip: the whole flash and, on some chips, \
+                 the option bytes are lost. This can't be undone.",
+            );
+            ui.horizontal(|ui| {
+                ui.checkbox(b_confirmed, "I understand the chip will be erased");
+                if ui
+                    .add_enabled(
+                        *b_confirmed,
+                        eframe::egui::Button::new("erase all and attach"),
+                    )
+                    .clicked()
+                {
+                    match h.attach_target_erase_all(probe_idx, target_chip) {
+                        Ok(_) => {
+                            h.get_core_num();
+                            b_attached = true;
+                        }
+                        Err(e) => {
+                            if let Some(p) = h.protection_error.as_mut() {
+                                p.message = format!("{}", e);
+                            }
+                        }
+                    }
+                    *b_confirmed = false;
+                }
+                if ui.button("dismiss").clicked() {
+                    h.protection_error = None;
+                    *b_confirmed = false;
+                }
+            });
+        });
+        b_attached
+    }
 }
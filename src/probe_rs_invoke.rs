@@ -18,20 +18,347 @@
 
 pub mod probe_rs_integration {
     use crate::configuration::m_config;
+    use crate::custom_targets::m_custom_targets::{is_custom_family, registry_generation};
     use probe_rs::{
-        config, flashing,
-        probe::{list, DebugProbeInfo},
-        rtt::{Rtt, ScanRegion},
-        Core, Permissions, Session,
+        architecture::{
+            arm::{
+                armv7m::Dhcsr,
+                component::TraceSink,
+                core::registers::{aarch32, aarch64, cortex_m},
+                memory::Component,
+                ArmChipInfo, ArmError, SwoConfig,
+            },
+            riscv::registers::RISCV_CORE_REGISTERS,
+        },
+        config::{self, ChipFamily, MemoryRegion, TargetSelector},
+        flashing,
+        probe::{list, DebugProbeInfo, Probe, WireProtocol},
+        rtt::{ChannelMode, Rtt, ScanRegion},
+        semihosting::SemihostingCommand,
+        BreakpointCause, Core, CoreDump, CoreRegister, CoreRegisters, CoreStatus, CoreType,
+        HaltReason, InstructionSet, MemoryInterface, MemoryMappedRegister, Permissions,
+        RegisterValue, Session,
     };
     use std::{
         borrow::{Borrow, BorrowMut},
+        collections::HashSet,
         error::Error,
         fs, io,
-        path::PathBuf,
+        num::NonZeroU32,
+        ops::Range,
+        path::{Path, PathBuf},
         time::{Duration, Instant},
     };
 
+    pub type RegisterSnapshot = Vec<(&'static CoreRegister, Option<u128>)>;
+
+    const DEMCR: u64 = 0xE000_EDFC;
+    const DEMCR_TRCENA: u32 = 1 << 24;
+    const DWT_CTRL: u64 = 0xE000_1000;
+    const DWT_CTRL_PCSAMPLENA: u32 = 1 << 12;
+    const DWT_CTRL_CYCTAP: u32 = 1 << 9;
+    const DWT_CTRL_POSTPRESET: u32 = 0xF << 1;
+    const DWT_PCSR: u64 = 0xE000_101C;
+    const DWT_COMP0: u64 = 0xE000_1020;
+    const DWT_FUNCTION_MATCHED: u32 = 1 << 24;
+    const SCB_CFSR: u64 = 0xE000_ED28;
+    const SCB_HFSR: u64 = 0xE000_ED2C;
+    const SCB_MMFAR: u64 = 0xE000_ED34;
+    const SCB_BFAR: u64 = 0xE000_ED38;
+    /// DBGMCU_IDCODE locations of the STM32 lines.
+    const STM32_DBGMCU_IDCODE: [u64; 4] = [0xE004_2000, 0x4001_5800, 0x5C00_1000, 0xE004_4000];
+    /// DEV_ID of the STM32 lines, by registry family. The registry variants carry
+    /// no part number, so the IDCODE can only be matched to a family.
+    const STM32_DEV_IDS: [(&str, &[u16]); 21] = [
+        ("STM32C0 Series", &[0x443, 0x453]),
+        ("STM32F0 Series", &[0x440, 0x442, 0x444, 0x445, 0x448]),
+        (
+            "STM32F1 Series",
+            &[0x410, 0x412, 0x414, 0x418, 0x420, 0x428, 0x430],
+        ),
+        ("STM32F2 Series", &[0x411]),
+        ("STM32F3 Series", &[0x422, 0x432, 0x438, 0x439, 0x446]),
+        (
+            "STM32F4 Series",
+            &[
+                0x413, 0x419, 0x421, 0x423, 0x431, 0x433, 0x434, 0x441, 0x458, 0x463,
+            ],
+        ),
+        ("STM32F7 Series", &[0x449, 0x451, 0x452]),
+        ("STM32G0 Series", &[0x456, 0x460, 0x466, 0x467]),
+        ("STM32G4 Series", &[0x468, 0x469, 0x479]),
+        ("STM32H5 Series", &[0x474, 0x478, 0x484]),
+        ("STM32H7 Series", &[0x450, 0x480, 0x483]),
+        ("STM32H7RS Series", &[0x485]),
+        ("STM32L0 Series", &[0x417, 0x425, 0x447, 0x457]),
+        ("STM32L1 Series", &[0x416, 0x427, 0x429, 0x436, 0x437]),
+        (
+            "STM32L4 Series",
+            &[0x415, 0x435, 0x461, 0x462, 0x464, 0x470, 0x471],
+        ),
+        ("STM32L5 Series", &[0x472]),
+        ("STM32U0 Series", &[0x459, 0x489]),
+        ("STM32U5 Series", &[0x455, 0x476, 0x481, 0x482]),
+        ("STM32WB Series", &[0x494, 0x495, 0x496]),
+        ("STM32WBA Series", &[0x492]),
+        ("STM32WL Series", &[0x497]),
+    ];
+    /// Generic descriptions attached to read identification registers.
+    const GENERIC_ARM_TARGET: &str = "Cortex-M0";
+    const GENERIC_RISCV_TARGET: &str = "riscv";
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    pub enum WatchpointAccess {
+        Read,
+        #[default]
+        Write,
+        ReadWrite,
+    }
+
+    /// Cortex-M System Control Block fault status and address registers.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct FaultStatus {
+        /// ARMv6-M cores don't have any of these.
+        pub b_available: bool,
+        pub cfsr: u32,
+        pub hfsr: u32,
+        pub mmfar: u32,
+        pub bfar: u32,
+    }
+
+    /// Semihosting file handle given to the target when it opens the console (":tt").
+    const SEMIHOSTING_TT_HANDLE: u32 = 1;
+
+    /// Outcome of servicing a semihosting request.
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum SemihostingEvent {
+        Output(String),
+        /// The target exited; the code is None when it didn't report one.
+        Exit(Option<u32>),
+        /// The request isn't serviced, the target got an error status and was resumed.
+        Unsupported(String),
+    }
+
+    pub struct ProtectionError {
+        pub message: String,
+        /// Probe and chip of the failed attach, the erase is only offered for them.
+        pub probe_idx: usize,
+        pub target_chip: String,
+        /// Whether the failed attach was under reset.
+        pub b_under_reset: bool,
+    }
+
+    impl ProtectionError {
+        pub fn matches(&self, probe_idx: usize, target_chip: &str) -> bool {
+            self.probe_idx == probe_idx && self.target_chip == target_chip
+        }
+    }
+
+    pub struct ChipCandidate {
+        pub family: String,
+        pub variants: Vec<String>,
+    }
+
+    #[derive(Default)]
+    pub struct ChipDetection {
+        /// What was read from the chip, or why it couldn't be, one line each.
+        pub identification: Vec<String>,
+        /// Target probe-rs identified unambiguously.
+        pub detected: Option<String>,
+        pub candidates: Vec<ChipCandidate>,
+    }
+
+    /// Registry targets of the manufacturer, narrowed to the variants with a
+    /// matching part number when there are any.
+    fn candidates_by_id(cc: u8, id: u8, parts: &[u16]) -> Vec<ChipCandidate> {
+        let families = config::families();
+        let by_maker: Vec<&ChipFamily> = families
+            .iter()
+            .filter(|f| f.manufacturer.is_some_and(|m| m.cc == cc && m.id == id))
+            .collect();
+        let by_part: Vec<ChipCandidate> = by_maker
+            .iter()
+            .filter_map(|f| {
+                let variants: Vec<String> = f
+                    .variants()
+                    .iter()
+                    .filter(|v| v.part.is_some_and(|p| parts.contains(&p)))
+                    .map(|v| v.name.clone())
+                    .collect();
+                (!variants.is_empty()).then(|| ChipCandidate {
+                    family: f.name.clone(),
+                    variants,
+                })
+            })
+            .collect();
+        if !by_part.is_empty() {
+            return by_part;
+        }
+        by_maker
+            .iter()
+            .map(|f| ChipCandidate {
+                family: f.name.clone(),
+                variants: f.variants().iter().map(|v| v.name.clone()).collect(),
+            })
+            .collect()
+    }
+
+    /// Manufacturer and part from the first class 1 ROM table found on an AP.
+    fn read_arm_chip_info(session: &mut Session) -> Result<Option<ArmChipInfo>, Box<dyn Error>> {
+        let interface = session.get_arm_interface()?;
+        let dp = interface.current_debug_port();
+        for ap in interface.access_ports(dp)? {
+            if let Ok(mut memory) = interface.memory_interface(&ap) {
+                let base = memory.base_address()?;
+                if let Component::Class1RomTable(id, _) = Component::try_parse(&mut *memory, base)?
+                {
+                    if let Some(jep106) = id.peripheral_id().jep106() {
+                        return Ok(Some(ArmChipInfo {
+                            manufacturer: jep106,
+                            part: id.peripheral_id().part(),
+                        }));
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Reads the ROM table and, on STM32, the DBGMCU IDCODE.
+    fn identify_arm(
+        session: &mut Session,
+        detection: &mut ChipDetection,
+    ) -> Result<bool, Box<dyn Error>> {
+        let Some(info) = read_arm_chip_info(session)? else {
+            return Ok(false);
+        };
+        detection
+            .identification
+            .push(format!("ARM ROM table: {}", info));
+        // STMicroelectronics
+        if info.manufacturer.cc == 0 && info.manufacturer.id == 0x20 {
+            let mut core = session.core(0)?;
+            // The other lines' locations may read as garbage, only a known DEV_ID counts.
+            let found = STM32_DBGMCU_IDCODE
+                .iter()
+                .filter_map(|addr| core.read_word_32(*addr).ok())
+                .find_map(|idcode| {
+                    let dev_id = (idcode & 0xFFF) as u16;
+                    STM32_DEV_IDS
+                        .iter()
+                        .find(|(_, ids)| ids.contains(&dev_id))
+                        .map(|(family, _)| (idcode, dev_id, *family))
+                });
+            if let Some((idcode, dev_id, family_name)) = found {
+                detection.identification.push(format!(
+                    "DBGMCU IDCODE {:#010x}: device {:#05x}, revision {:#06x}, {}",
+                    idcode,
+                    dev_id,
+                    idcode >> 16,
+                    family_name
+                ));
+                if let Some(family) = config::families()
+                    .into_iter()
+                    .find(|f| f.name == family_name)
+                {
+                    detection.candidates = vec![ChipCandidate {
+                        family: family.name.clone(),
+                        variants: family.variants().iter().map(|v| v.name.clone()).collect(),
+                    }];
+                    return Ok(true);
+                }
+            } else {
+                detection.identification.push(
+                    "DBGMCU IDCODE: no known STM32 device, candidates are per manufacturer"
+                        .to_owned(),
+                );
+            }
+        }
+        detection.candidates =
+            candidates_by_id(info.manufacturer.cc, info.manufacturer.id, &[info.part]);
+        Ok(true)
+    }
+
+    /// Reads the JTAG idcode of the first hart.
+    fn identify_riscv(
+        session: &mut Session,
+        detection: &mut ChipDetection,
+    ) -> Result<bool, Box<dyn Error>> {
+        let mut interface = session.get_riscv_interface(0)?;
+        let Some(idcode) = interface.read_idcode()? else {
+            return Ok(false);
+        };
+        let cc = ((idcode >> 8) & 0x0F) as u8;
+        let id = ((idcode >> 1) & 0x7F) as u8;
+        let part = ((idcode >> 12) & 0xFFFF) as u16;
+        detection.identification.push(format!(
+            "RISC-V idcode {:#010x}: manufacturer bank {} id {:#04x}, part {:#06x}",
+            idcode, cc, id, part
+        ));
+        detection.candidates = candidates_by_id(cc, id, &[part]);
+        Ok(true)
+    }
+
+    /// Whether an attach error comes from read-out protection, which probe-rs can
+    /// only lift by erasing the chip.
+    fn is_protection_error(e: &probe_rs::Error) -> bool {
+        matches!(
+            e,
+            probe_rs::Error::MissingPermissions(_)
+                | probe_rs::Error::Arm(ArmError::MissingPermissions(_))
+        )
+    }
+
+    /// Register file of the dumped core, None for architectures without one.
+    fn core_dump_registers(dump: &CoreDump) -> Option<&'static CoreRegisters> {
+        let regs: &'static CoreRegisters = match dump.core_type {
+            t if t.is_cortex_m() => {
+                if dump.fpu_support {
+                    &cortex_m::CORTEX_M_WITH_FP_CORE_REGISTERS
+                } else {
+                    &cortex_m::CORTEX_M_CORE_REGISTERS
+                }
+            }
+            CoreType::Armv8a if dump.instruction_set == InstructionSet::A64 => {
+                &aarch64::AARCH64_CORE_REGISTERS
+            }
+            CoreType::Armv7a | CoreType::Armv8a => match dump.floating_point_register_count {
+                Some(32) => &aarch32::AARCH32_WITH_FP_32_CORE_REGISTERS,
+                Some(16) => &aarch32::AARCH32_WITH_FP_16_CORE_REGISTERS,
+                _ => &aarch32::AARCH32_CORE_REGISTERS,
+            },
+            CoreType::Riscv => &RISCV_CORE_REGISTERS,
+            _ => return None,
+        };
+        Some(regs)
+    }
+
+    fn read_memory_words<M: MemoryInterface + ?Sized>(
+        mem: &mut M,
+        address: u64,
+        word_size: usize,
+        buf: &mut [u8],
+    ) -> Result<(), probe_rs::Error> {
+        match word_size {
+            4 => {
+                let mut words = vec![0u32; buf.len() / 4];
+                mem.read_32(address, &mut words)?;
+                for (b, w) in buf.chunks_exact_mut(4).zip(words) {
+                    b.copy_from_slice(&w.to_le_bytes());
+                }
+            }
+            2 => {
+                let mut words = vec![0u16; buf.len() / 2];
+                mem.read_16(address, &mut words)?;
+                for (b, w) in buf.chunks_exact_mut(2).zip(words) {
+                    b.copy_from_slice(&w.to_le_bytes());
+                }
+            }
+            _ => mem.read_8(address, buf)?,
+        }
+        Ok(())
+    }
+
     fn get_rtt_symbol<T: io::Read + io::Seek>(file: &mut T) -> Option<u64> {
         get_symbol(file, m_config::RTT_SYMBOL)
     }
@@ -56,11 +383,29 @@ pub mod probe_rs_integration {
     pub struct ProbeRsHandler {
         pub probes_list: Vec<DebugProbeInfo>,
         pub chips_list: Vec<String>,
+        /// Chips from target descriptions loaded at runtime.
+        pub custom_chips: HashSet<String>,
+        chips_generation: usize,
         pub up_chs_size: usize,
         pub session: Option<Session>,
         pub rtt: Option<Rtt>,
         pub target_cores_num: usize,
         pub scan_region: Option<ScanRegion>,
+        /// Set while a core dump is inspected instead of a live target.
+        pub core_dump: Option<CoreDump>,
+        /// Wire protocol to select before attaching, None for the probe's default.
+        pub protocol: Option<WireProtocol>,
+        /// Clock to request before attaching in kHz, 0 for the probe's default.
+        pub speed_khz: u32,
+        /// Protocol and clock the probe settled on for the current session.
+        pub active_protocol: Option<WireProtocol>,
+        pub active_speed_khz: Option<u32>,
+        /// Set when the last attach failed because the chip is locked.
+        pub protection_error: Option<ProtectionError>,
+        /// Result of the last target auto-detection.
+        pub chip_detection: Option<ChipDetection>,
+        /// Bumped on every attach, so views can tell cached target state is stale.
+        pub session_id: usize,
     }
 
     impl ProbeRsHandler {
@@ -71,30 +416,168 @@ pub mod probe_rs_integration {
             &self.probes_list
         }
 
-        pub fn attach_target(
+        /// Opens the probe with the configured protocol and speed.
+        fn open_probe(&mut self, probe_idx: usize) -> Result<Probe, Box<dyn Error>> {
+            let mut p = self.probes_list[probe_idx].open()?;
+            if let Some(protocol) = self.protocol {
+                p.select_protocol(protocol)?;
+            }
+            if self.speed_khz > 0 {
+                p.set_speed(self.speed_khz)?;
+            }
+            self.active_protocol = p.protocol().or(self.protocol);
+            self.active_speed_khz = Some(p.speed_khz());
+            Ok(p)
+        }
+
+        fn attach_with(
             &mut self,
             probe_idx: usize,
             target_chip: &str,
+            permissions: Permissions,
+            b_under_reset: bool,
         ) -> Result<&Option<Session>, Box<dyn Error>> {
             if self.session.is_none() && probe_idx < self.probes_list.len() {
-                let p = self.probes_list[probe_idx].open()?;
-                let s = p.attach(target_chip, Permissions::default())?;
+                // A stale prompt must never offer to erase another chip.
+                self.protection_error = None;
+                let p = self.open_probe(probe_idx)?;
+                let rst = if b_under_reset {
+                    p.attach_under_reset(target_chip, permissions)
+                } else {
+                    p.attach(target_chip, permissions)
+                };
+                let s = match rst {
+                    Ok(s) => s,
+                    Err(e) => {
+                        if is_protection_error(&e) {
+                            self.protection_error = Some(ProtectionError {
+                                message: format!("{}", e),
+                                probe_idx,
+                                target_chip: target_chip.to_owned(),
+                                b_under_reset,
+                            });
+                        }
+                        return Err(e.into());
+                    }
+                };
                 self.session = Some(s);
+                self.core_dump = None;
+                self.protection_error = None;
+                self.chip_detection = None;
+                self.session_id += 1;
             }
             Ok(&self.session)
         }
 
+        /// Identifies the connected chip and proposes registry targets. probe-rs'
+        /// own detection is tried first, when it can't settle on a single target the
+        /// identification registers are read through a generic core description.
+        pub fn detect_chip(&mut self, probe_idx: usize) -> &ChipDetection {
+            let mut detection = ChipDetection::default();
+            if let Err(e) = self.try_detect_chip(probe_idx, &mut detection) {
+                detection.identification.push(format!("{}", e));
+            }
+            self.chip_detection.insert(detection)
+        }
+
+        fn try_detect_chip(
+            &mut self,
+            probe_idx: usize,
+            detection: &mut ChipDetection,
+        ) -> Result<(), Box<dyn Error>> {
+            if self.session.is_some() {
+                return Err("detach before auto-detecting the target".into());
+            }
+            if probe_idx >= self.probes_list.len() {
+                return Err("no probe selected".into());
+            }
+
+            let p = self.open_probe(probe_idx)?;
+            match p.attach(TargetSelector::Auto, Permissions::default()) {
+                Ok(mut session) => {
+                    let name = session.target().name.clone();
+                    if let Ok(Some(info)) = read_arm_chip_info(&mut session) {
+                        detection
+                            .identification
+                            .push(format!("ARM ROM table: {}", info));
+                    }
+                    let (_, family) = config::get_target_and_family_by_name(&name)?;
+                    let mut variants = vec![name.clone()];
+                    variants.extend(
+                        family
+                            .variants()
+                            .iter()
+                            .map(|v| v.name.clone())
+                            .filter(|v| *v != name),
+                    );
+                    detection.candidates.push(ChipCandidate {
+                        family: family.name.clone(),
+                        variants,
+                    });
+                    detection.detected = Some(name);
+                    return Ok(());
+                }
+                Err(e) => detection
+                    .identification
+                    .push(format!("probe-rs detection: {}", e)),
+            }
+
+            let arm = self.open_probe(probe_idx).and_then(|p| {
+                let mut session = p.attach(GENERIC_ARM_TARGET, Permissions::default())?;
+                identify_arm(&mut session, detection)
+            });
+            match arm {
+                Ok(true) => return Ok(()),
+                Ok(false) => {}
+                Err(e) => detection.identification.push(format!("ARM: {}", e)),
+            }
+            let riscv = self.open_probe(probe_idx).and_then(|p| {
+                let mut session = p.attach(GENERIC_RISCV_TARGET, Permissions::default())?;
+                identify_riscv(&mut session, detection)
+            });
+            match riscv {
+                Ok(true) => {}
+                Ok(false) => detection
+                    .identification
+                    .push("RISC-V: no idcode".to_owned()),
+                Err(e) => detection.identification.push(format!("RISC-V: {}", e)),
+            }
+            Ok(())
+        }
+
+        pub fn attach_target(
+            &mut self,
+            probe_idx: usize,
+            target_chip: &str,
+        ) -> Result<&Option<Session>, Box<dyn Error>> {
+            self.attach_with(probe_idx, target_chip, Permissions::default(), false)
+        }
+
         pub fn attach_target_under_reset(
             &mut self,
             probe_idx: usize,
             target_chip: &str,
         ) -> Result<&Option<Session>, Box<dyn Error>> {
-            if self.session.is_none() && probe_idx < self.probes_list.len() {
-                let p = self.probes_list[probe_idx].open()?;
-                let s = p.attach_under_reset(target_chip, Permissions::default())?;
-                self.session = Some(s);
-            }
-            Ok(&self.session)
+            self.attach_with(probe_idx, target_chip, Permissions::default(), true)
+        }
+
+        /// Attaches the way that failed with `protection_error`, allowing probe-rs to
+        /// mass erase the chip to unlock it.
+        pub fn attach_target_erase_all(
+            &mut self,
+            probe_idx: usize,
+            target_chip: &str,
+        ) -> Result<&Option<Session>, Box<dyn Error>> {
+            let Some(b_under_reset) = self
+                .protection_error
+                .as_ref()
+                .filter(|p| p.matches(probe_idx, target_chip))
+                .map(|p| p.b_under_reset)
+            else {
+                return Err("no failed attach to this chip needs unlocking".into());
+            };
+            let permissions = Permissions::new().allow_erase_all();
+            self.attach_with(probe_idx, target_chip, permissions, b_under_reset)
         }
 
         pub fn try_to_download(
@@ -118,27 +601,250 @@ pub mod probe_rs_integration {
             Ok(())
         }
 
+        pub fn core_status(
+            &mut self,
+            core_idx: usize,
+        ) -> Result<Option<CoreStatus>, Box<dyn Error>> {
+            if self.core_dump.is_some() {
+                return Ok(Some(CoreStatus::Halted(HaltReason::Request)));
+            }
+            let mut status = None;
+            if let Some(mut core) = self.get_core(core_idx)? {
+                status = Some(core.status()?);
+            }
+            Ok(status)
+        }
+
+        pub fn halt_core(
+            &mut self,
+            core_idx: usize,
+            timeout: Duration,
+        ) -> Result<(), Box<dyn Error>> {
+            if let Some(mut core) = self.get_core(core_idx)? {
+                core.halt(timeout)?;
+            }
+            Ok(())
+        }
+
+        pub fn run_core(&mut self, core_idx: usize) -> Result<(), Box<dyn Error>> {
+            if let Some(mut core) = self.get_core(core_idx)? {
+                core.run()?;
+            }
+            Ok(())
+        }
+
+        pub fn step_core(&mut self, core_idx: usize) -> Result<(), Box<dyn Error>> {
+            if let Some(mut core) = self.get_core(core_idx)? {
+                core.step()?;
+            }
+            Ok(())
+        }
+
+        pub fn reset_core(&mut self, core_idx: usize) -> Result<(), Box<dyn Error>> {
+            if let Some(mut core) = self.get_core(core_idx)? {
+                core.reset()?;
+            }
+            Ok(())
+        }
+
+        pub fn reset_and_halt_core(
+            &mut self,
+            core_idx: usize,
+            timeout: Duration,
+        ) -> Result<(), Box<dyn Error>> {
+            if let Some(mut core) = self.get_core(core_idx)? {
+                core.reset_and_halt(timeout)?;
+            }
+            Ok(())
+        }
+
+        pub fn read_core_registers(
+            &mut self,
+            core_idx: usize,
+        ) -> Result<RegisterSnapshot, Box<dyn Error>> {
+            let mut regs = Vec::new();
+            if let Some(dump) = self.core_dump.as_ref() {
+                if let Some(registers) = core_dump_registers(dump) {
+                    for r in registers.all_registers() {
+                        let value = dump
+                            .registers
+                            .get(&r.id())
+                            .and_then(|v| (*v).try_into().ok());
+                        regs.push((r, value));
+                    }
+                }
+                return Ok(regs);
+            }
+            if let Some(mut core) = self.get_core(core_idx)? {
+                let registers = core.registers();
+                for r in registers.all_registers() {
+                    // Some registers (e.g. FPU ones with the FPU disabled) can't be read,
+                    // keep them listed anyway.
+                    let value = core.read_core_reg::<u128>(r.id()).ok();
+                    regs.push((r, value));
+                }
+            }
+            Ok(regs)
+        }
+
+        pub fn write_core_register(
+            &mut self,
+            core_idx: usize,
+            reg: &CoreRegister,
+            value: u128,
+        ) -> Result<(), Box<dyn Error>> {
+            if let Some(mut core) = self.get_core(core_idx)? {
+                let value = match reg.size_in_bits() {
+                    0..=32 => RegisterValue::U32(u32::try_from(value)?),
+                    33..=64 => RegisterValue::U64(u64::try_from(value)?),
+                    _ => RegisterValue::U128(value),
+                };
+                core.write_core_reg(reg.id(), value)?;
+            }
+            Ok(())
+        }
+
+        pub fn core_type(&mut self, core_idx: usize) -> Result<Option<CoreType>, Box<dyn Error>> {
+            if let Some(dump) = self.core_dump.as_ref() {
+                return Ok(Some(dump.core_type()));
+            }
+            Ok(self.get_core(core_idx)?.map(|c| c.core_type()))
+        }
+
+        pub fn read_pc(&mut self, core_idx: usize) -> Result<Option<u64>, Box<dyn Error>> {
+            if let Some(dump) = self.core_dump.as_ref() {
+                let pc = core_dump_registers(dump)
+                    .and_then(|r| r.pc())
+                    .and_then(|r| dump.registers.get(&r.id()))
+                    .and_then(|v| (*v).try_into().ok());
+                return Ok(pc);
+            }
+            let mut pc = None;
+            if let Some(mut core) = self.get_core(core_idx)? {
+                let id = core.program_counter().id();
+                pc = Some(core.read_core_reg::<u64>(id)?);
+            }
+            Ok(pc)
+        }
+
+        /// Hardware breakpoint comparators of the core, None when not attached.
+        pub fn breakpoint_units(&mut self, core_idx: usize) -> Result<Option<u32>, Box<dyn Error>> {
+            match self.get_core(core_idx)? {
+                Some(mut core) => Ok(Some(core.available_breakpoint_units()?)),
+                None => Ok(None),
+            }
+        }
+
+        pub fn set_breakpoint(
+            &mut self,
+            core_idx: usize,
+            address: u64,
+        ) -> Result<(), Box<dyn Error>> {
+            if let Some(mut core) = self.get_core(core_idx)? {
+                core.set_hw_breakpoint(address)?;
+            }
+            Ok(())
+        }
+
+        pub fn clear_breakpoint(
+            &mut self,
+            core_idx: usize,
+            address: u64,
+        ) -> Result<(), Box<dyn Error>> {
+            if let Some(mut core) = self.get_core(core_idx)? {
+                core.clear_hw_breakpoint(address)?;
+            }
+            Ok(())
+        }
+
+        /// Reads `buf.len()` bytes at `address` using accesses of `word_size` bytes,
+        /// so peripheral registers see the bus width they expect.
+        pub fn read_memory(
+            &mut self,
+            core_idx: usize,
+            address: u64,
+            word_size: usize,
+            buf: &mut [u8],
+        ) -> Result<(), Box<dyn Error>> {
+            if let Some(dump) = self.core_dump.as_mut() {
+                read_memory_words(dump, address, word_size, buf)?;
+            } else if let Some(mut core) = self.get_core(core_idx)? {
+                read_memory_words(&mut core, address, word_size, buf)?;
+            }
+            Ok(())
+        }
+
+        pub fn write_memory(
+            &mut self,
+            core_idx: usize,
+            address: u64,
+            data: &[u8],
+        ) -> Result<(), Box<dyn Error>> {
+            if let Some(mut core) = self.get_core(core_idx)? {
+                core.write_8(address, data)?;
+            }
+            Ok(())
+        }
+
+        pub fn write_memory_word(
+            &mut self,
+            core_idx: usize,
+            address: u64,
+            word_size: usize,
+            value: u32,
+        ) -> Result<(), Box<dyn Error>> {
+            if let Some(mut core) = self.get_core(core_idx)? {
+                match word_size {
+                    4 => core.write_word_32(address, value)?,
+                    2 => core.write_word_16(address, u16::try_from(value)?)?,
+                    _ => core.write_word_8(address, u8::try_from(value)?)?,
+                }
+            }
+            Ok(())
+        }
+
         pub fn get_availabe_chips(&mut self) -> &Vec<String> {
-            if self.chips_list.is_empty() {
+            let generation = registry_generation();
+            if self.chips_list.is_empty() || self.chips_generation != generation {
+                self.chips_list.clear();
+                self.custom_chips.clear();
                 for family in config::families() {
+                    let b_custom = is_custom_family(&family.name);
                     for variant in family.variants() {
                         let v = variant.name.clone();
+                        if b_custom {
+                            self.custom_chips.insert(v.clone());
+                        }
                         self.chips_list.push(v);
                     }
                 }
+                self.chips_generation = generation;
             }
             &self.chips_list
         }
 
+        /// Name to show in the target list, custom targets are marked.
+        pub fn chip_label(&self, chip: &str) -> String {
+            if self.custom_chips.contains(chip) {
+                format!("{} (custom)", chip)
+            } else {
+                chip.to_owned()
+            }
+        }
+
         pub fn get_core_num(&mut self) -> usize {
-            if let Some(s) = self.session.borrow() {
+            if self.core_dump.is_some() {
+                self.target_cores_num = 1;
+            } else if let Some(s) = self.session.borrow() {
                 self.target_cores_num = s.list_cores().len();
             }
             self.target_cores_num
         }
 
-        #[allow(dead_code)]
-        pub fn get_core(&mut self, core_idx: usize) -> Result<Option<Core>, Box<dyn Error>> {
+        pub fn get_core(&mut self, core_idx: usize) -> Result<Option<Core<'_>>, Box<dyn Error>> {
+            if self.core_dump.is_some() {
+                return Err("a core dump is open, it can only be inspected".into());
+            }
             let mut opt_core = None;
             if let Some(s) = self.session.borrow_mut() {
                 if core_idx < self.target_cores_num {
@@ -225,24 +931,22 @@ pub mod probe_rs_integration {
                                     return Ok(&self.rtt);
                                 }
                                 Err(e) => {
-                                    if matches!(e, probe_rs::rtt::Error::ControlBlockNotFound) {
-                                        std::thread::sleep(Duration::from_millis(50));
-                                        continue;
+                                    if !matches!(e, probe_rs::rtt::Error::ControlBlockNotFound) {
+                                        self.rtt = None;
+                                        return Ok(&self.rtt);
                                     }
-
-                                    self.rtt = None;
-                                    return Ok(&self.rtt);
                                 }
                             }
                         }
                         if Instant::now().duration_since(start) <= timeout {
+                            std::thread::sleep(Duration::from_millis(50));
                             continue;
                         } else {
                             break;
                         }
                     }
                     // Timeout reached
-                    self.rtt = Some(Rtt::attach(&mut core).expect("RTT attach"));
+                    self.rtt = Some(Rtt::attach(&mut core)?);
                 }
             }
 
@@ -278,5 +982,378 @@ pub mod probe_rs_integration {
             }
             Ok(count)
         }
+
+        pub fn rtt_up_channel_buffer_size(&mut self, ch_number: usize) -> usize {
+            if let Some(r) = self.rtt.borrow_mut() {
+                if let Some(up_ch) = r.up_channel(ch_number) {
+                    return up_ch.buffer_size();
+                }
+            }
+            0
+        }
+
+        pub fn rtt_channel_mode(
+            &mut self,
+            core_idx: usize,
+            ch_number: usize,
+        ) -> Result<Option<ChannelMode>, Box<dyn Error>> {
+            let mut mode = None;
+            if let Some(s) = self.session.borrow_mut() {
+                if core_idx < self.target_cores_num {
+                    // Select a core.
+                    let mut core = s.core(core_idx)?;
+                    if let Some(r) = self.rtt.borrow_mut() {
+                        if let Some(up_ch) = r.up_channel(ch_number) {
+                            mode = Some(up_ch.mode(&mut core)?);
+                        }
+                    }
+                }
+            }
+            Ok(mode)
+        }
+
+        pub fn rtt_set_channel_mode(
+            &mut self,
+            core_idx: usize,
+            ch_number: usize,
+            mode: ChannelMode,
+        ) -> Result<(), Box<dyn Error>> {
+            if let Some(s) = self.session.borrow_mut() {
+                if core_idx < self.target_cores_num {
+                    // Select a core.
+                    let mut core = s.core(core_idx)?;
+                    if let Some(r) = self.rtt.borrow_mut() {
+                        if let Some(up_ch) = r.up_channel(ch_number) {
+                            up_ch.set_mode(&mut core, mode)?;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        pub fn detach_rtt(&mut self) {
+            self.rtt = None;
+            self.up_chs_size = 0;
+        }
+
+        pub fn rtt_control_block_valid(&mut self, core_idx: usize) -> Result<bool, Box<dyn Error>> {
+            let mut valid = false;
+            if let Some(s) = self.session.borrow_mut() {
+                if core_idx < self.target_cores_num {
+                    if let Some(r) = self.rtt.borrow() {
+                        // Select a core.
+                        let mut core = s.core(core_idx)?;
+                        // The control block starts with its ID string, which the firmware
+                        // rewrites on every boot and which is gone after flash/RAM is cleared.
+                        let mut id = [0u8; 16];
+                        core.read(r.ptr(), &mut id)?;
+                        valid = id.starts_with(m_config::RTT_CONTROL_BLOCK_ID.as_bytes());
+                    }
+                }
+            }
+            Ok(valid)
+        }
+
+        pub fn core_reset_detected(&mut self, core_idx: usize) -> Result<bool, Box<dyn Error>> {
+            let mut reset = false;
+            if let Some(s) = self.session.borrow_mut() {
+                if core_idx < self.target_cores_num {
+                    // Select a core.
+                    let mut core = s.core(core_idx)?;
+                    // Only Cortex-M exposes a sticky reset flag (DHCSR.S_RESET_ST, cleared on read).
+                    if core.core_type().is_cortex_m() {
+                        let dhcsr = Dhcsr::from(core.read_word_32(Dhcsr::get_mmio_address())?);
+                        reset = dhcsr.s_reset_st();
+                    }
+                }
+            }
+            Ok(reset)
+        }
+
+        /// probe-rs has no watchpoint API, so the Cortex-M DWT comparators are driven
+        /// directly. Returns the core only when it has a DWT.
+        fn get_dwt_core(&mut self, core_idx: usize) -> Result<Option<Core<'_>>, Box<dyn Error>> {
+            let core = self.get_core(core_idx)?;
+            if let Some(c) = core.as_ref() {
+                if !c.core_type().is_cortex_m() {
+                    return Err("data watchpoints are only supported on Cortex-M cores".into());
+                }
+            }
+            Ok(core)
+        }
+
+        /// DWT comparators of the core, None when not attached.
+        pub fn watchpoint_units(&mut self, core_idx: usize) -> Result<Option<u32>, Box<dyn Error>> {
+            match self.get_dwt_core(core_idx)? {
+                Some(mut core) => Ok(Some(core.read_word_32(DWT_CTRL)? >> 28)),
+                None => Ok(None),
+            }
+        }
+
+        pub fn set_watchpoint(
+            &mut self,
+            core_idx: usize,
+            unit: usize,
+            address: u64,
+            size: u32,
+            access: WatchpointAccess,
+        ) -> Result<(), Box<dyn Error>> {
+            if let Some(mut core) = self.get_dwt_core(core_idx)? {
+                let demcr = core.read_word_32(DEMCR)?;
+                core.write_word_32(DEMCR, demcr | DEMCR_TRCENA)?;
+                let comp = DWT_COMP0 + unit as u64 * 16;
+                let size_log2 = size.trailing_zeros();
+                let function = if core.core_type() == CoreType::Armv8m {
+                    // MATCH = data address, ACTION = debug event, DATAVSIZE = size.
+                    let m = match access {
+                        WatchpointAccess::ReadWrite => 0b0100,
+                        WatchpointAccess::Write => 0b0101,
+                        WatchpointAccess::Read => 0b0110,
+                    };
+                    m | (0b01 << 4) | (size_log2 << 10)
+                } else {
+                    core.write_word_32(comp + 4, size_log2)?;
+                    match access {
+                        WatchpointAccess::Read => 0b0101,
+                        WatchpointAccess::Write => 0b0110,
+                        WatchpointAccess::ReadWrite => 0b0111,
+                    }
+                };
+                core.write_word_32(comp, u32::try_from(address)?)?;
+                core.write_word_32(comp + 8, function)?;
+            }
+            Ok(())
+        }
+
+        pub fn clear_watchpoint(
+            &mut self,
+            core_idx: usize,
+            unit: usize,
+        ) -> Result<(), Box<dyn Error>> {
+            if let Some(mut core) = self.get_dwt_core(core_idx)? {
+                core.write_word_32(DWT_COMP0 + unit as u64 * 16 + 8, 0)?;
+            }
+            Ok(())
+        }
+
+        /// Reads (and thereby clears) the MATCHED flag of a DWT comparator.
+        pub fn watchpoint_matched(
+            &mut self,
+            core_idx: usize,
+            unit: usize,
+        ) -> Result<bool, Box<dyn Error>> {
+            let mut matched = false;
+            if let Some(mut core) = self.get_dwt_core(core_idx)? {
+                let function = core.read_word_32(DWT_COMP0 + unit as u64 * 16 + 8)?;
+                matched = function & DWT_FUNCTION_MATCHED != 0;
+            }
+            Ok(matched)
+        }
+
+        pub fn read_fault_status(
+            &mut self,
+            core_idx: usize,
+        ) -> Result<FaultStatus, Box<dyn Error>> {
+            let mut status = FaultStatus::default();
+            if let Some(mut core) = self.get_core(core_idx)? {
+                let core_type = core.core_type();
+                if !core_type.is_cortex_m() {
+                    return Err("fault analysis is only supported on Cortex-M cores".into());
+                }
+                // ARMv6-M only has HardFault and none of these registers.
+                if core_type != CoreType::Armv6m {
+                    status.b_available = true;
+                    status.hfsr = core.read_word_32(SCB_HFSR)?;
+                    status.cfsr = core.read_word_32(SCB_CFSR)?;
+                    status.mmfar = core.read_word_32(SCB_MMFAR)?;
+                    status.bfar = core.read_word_32(SCB_BFAR)?;
+                }
+            }
+            Ok(status)
+        }
+
+        /// Services the semihosting request the core is halted on, if any, and
+        /// resumes it unless the target exited.
+        pub fn service_semihosting(
+            &mut self,
+            core_idx: usize,
+        ) -> Result<Option<SemihostingEvent>, Box<dyn Error>> {
+            let Some(mut core) = self.get_core(core_idx)? else {
+                return Ok(None);
+            };
+            let CoreStatus::Halted(HaltReason::Breakpoint(BreakpointCause::Semihosting(cmd))) =
+                core.status()?
+            else {
+                return Ok(None);
+            };
+            let event = match cmd {
+                SemihostingCommand::ExitSuccess => {
+                    return Ok(Some(SemihostingEvent::Exit(Some(0))))
+                }
+                SemihostingCommand::ExitError(details) => {
+                    return Ok(Some(SemihostingEvent::Exit(details.exit_status)))
+                }
+                SemihostingCommand::WriteConsole(req) => {
+                    SemihostingEvent::Output(req.read(&mut core)?)
+                }
+                SemihostingCommand::Open(req) if req.path(&mut core)? == ":tt" => {
+                    // newlib opens ":tt" for stdout/stderr before writing to it.
+                    if let Some(handle) = NonZeroU32::new(SEMIHOSTING_TT_HANDLE) {
+                        req.respond_with_handle(&mut core, handle)?;
+                    }
+                    SemihostingEvent::Output(String::new())
+                }
+                SemihostingCommand::Close(req)
+                    if req.file_handle(&mut core)? == SEMIHOSTING_TT_HANDLE =>
+                {
+                    req.success(&mut core)?;
+                    SemihostingEvent::Output(String::new())
+                }
+                SemihostingCommand::Write(req) if req.file_handle() == SEMIHOSTING_TT_HANDLE => {
+                    let data = req.read(&mut core)?;
+                    // The status is the number of bytes not written.
+                    req.write_status(&mut core, 0)?;
+                    SemihostingEvent::Output(String::from_utf8_lossy(&data).into_owned())
+                }
+                other => SemihostingEvent::Unsupported(format!("{:?}", other)),
+            };
+            core.run()?;
+            Ok(Some(event))
+        }
+
+        /// Routes the ITM/DWT trace of the core to SWO at `baud` and starts the
+        /// capture on the probe. `tpiu_clk` is the trace clock, usually the core clock.
+        pub fn setup_swo(
+            &mut self,
+            core_idx: usize,
+            tpiu_clk: u32,
+            baud: u32,
+            b_pc_sampling: bool,
+        ) -> Result<(), Box<dyn Error>> {
+            if let Some(s) = self.session.borrow_mut() {
+                if core_idx >= self.target_cores_num {
+                    return Ok(());
+                }
+                let config = SwoConfig::new(tpiu_clk).set_baud(baud);
+                s.setup_tracing(core_idx, TraceSink::Swo(config))?;
+                let mut core = s.core(core_idx)?;
+                let mut ctrl = core.read_word_32(DWT_CTRL)?;
+                if b_pc_sampling {
+                    // Sample every 16 * 1024 cycles.
+                    ctrl |= DWT_CTRL_PCSAMPLENA | DWT_CTRL_CYCTAP | DWT_CTRL_POSTPRESET;
+                } else {
+                    ctrl &= !DWT_CTRL_PCSAMPLENA;
+                }
+                core.write_word_32(DWT_CTRL, ctrl)?;
+            }
+            Ok(())
+        }
+
+        /// Reads the SWO data the probe received since the last call.
+        pub fn read_swo(&mut self) -> Result<Vec<u8>, Box<dyn Error>> {
+            let mut data = Vec::new();
+            if let Some(s) = self.session.borrow_mut() {
+                data = s.read_trace_data()?;
+            }
+            Ok(data)
+        }
+
+        pub fn stop_swo(&mut self, core_idx: usize) -> Result<(), Box<dyn Error>> {
+            if let Some(mut core) = self.get_core(core_idx)? {
+                let ctrl = core.read_word_32(DWT_CTRL)?;
+                core.write_word_32(DWT_CTRL, ctrl & !DWT_CTRL_PCSAMPLENA)?;
+            }
+            if let Some(s) = self.session.borrow_mut() {
+                if core_idx < self.target_cores_num {
+                    s.disable_swv(core_idx)?;
+                }
+            }
+            Ok(())
+        }
+
+        /// Enables the DWT so its PC sample register can be read while the core runs.
+        /// Returns false when the core isn't a Cortex-M.
+        pub fn enable_pc_sampling_register(
+            &mut self,
+            core_idx: usize,
+        ) -> Result<bool, Box<dyn Error>> {
+            if let Some(mut core) = self.get_core(core_idx)? {
+                if !core.core_type().is_cortex_m() {
+                    return Ok(false);
+                }
+                let demcr = core.read_word_32(DEMCR)?;
+                core.write_word_32(DEMCR, demcr | DEMCR_TRCENA)?;
+                return Ok(true);
+            }
+            Ok(false)
+        }
+
+        /// Reads DWT_PCSR, None when there is no sample, e.g. the core is halted or
+        /// the register isn't implemented.
+        pub fn read_pc_sampling_register(
+            &mut self,
+            core_idx: usize,
+        ) -> Result<Option<u32>, Box<dyn Error>> {
+            let mut sample = None;
+            if let Some(mut core) = self.get_core(core_idx)? {
+                let pc = core.read_word_32(DWT_PCSR)?;
+                if pc != 0 && pc != 0xFFFF_FFFF {
+                    sample = Some(pc);
+                }
+            }
+            Ok(sample)
+        }
+
+        /// RAM regions of the attached target, the usual contents of a core dump.
+        pub fn ram_regions(&self) -> Vec<Range<u64>> {
+            let mut regions = Vec::new();
+            if let Some(s) = self.session.borrow() {
+                for r in s.target().memory_map.iter() {
+                    if let MemoryRegion::Ram(ram) = r {
+                        regions.push(ram.range.clone());
+                    }
+                }
+            }
+            regions
+        }
+
+        /// Saves the registers of the core and the memory in `ranges` to `path`. A
+        /// running core is halted for the capture and resumed afterwards.
+        pub fn save_core_dump(
+            &mut self,
+            core_idx: usize,
+            ranges: Vec<Range<u64>>,
+            path: &Path,
+        ) -> Result<(), Box<dyn Error>> {
+            let Some(mut core) = self.get_core(core_idx)? else {
+                return Err("not attached".into());
+            };
+            let b_running = !core.core_halted()?;
+            if b_running {
+                core.halt(Duration::from_millis(m_config::CORE_HALT_TIMEOUT_MS))?;
+            }
+            let dump = CoreDump::dump_core(&mut core, ranges);
+            if b_running {
+                core.run()?;
+            }
+            dump?.store(path)?;
+            Ok(())
+        }
+
+        /// Drops the probe session and serves reads from the core dump at `path`.
+        pub fn open_core_dump(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+            let dump = CoreDump::load(path)?;
+            self.rtt = None;
+            self.session = None;
+            self.core_dump = Some(dump);
+            self.get_core_num();
+            Ok(())
+        }
+
+        pub fn close_core_dump(&mut self) {
+            self.core_dump = None;
+            self.target_cores_num = 0;
+        }
     }
 }
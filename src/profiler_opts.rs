@@ -0,0 +1,381 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_profiler_opts {
+    use crate::call_stack_opts::m_call_stack_opts::unwind;
+    use crate::configuration::m_config;
+    use crate::dwarf_info::m_dwarf_info::DwarfInfo;
+    use crate::elf_symbols::m_elf_symbols::ElfSymbols;
+    use crate::probe_rs_invoke::probe_rs_integration::ProbeRsHandler;
+    use eframe::egui::{pos2, vec2, Align2, Color32, FontId, Pos2, Rect, Sense};
+    use probe_rs::CoreStatus;
+    use std::{
+        collections::HashMap,
+        time::{Duration, Instant},
+    };
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    enum SampleMethod {
+        /// Read DWT_PCSR while the core runs, Cortex-M only.
+        #[default]
+        Pcsr,
+        /// Halt the core, read its PC and resume it.
+        Halt,
+    }
+
+    #[derive(Default)]
+    struct FlameNode {
+        name: String,
+        samples: u64,
+        children: Vec<FlameNode>,
+    }
+
+    impl FlameNode {
+        /// Adds a sample for `path`, outermost function first.
+        fn insert(&mut self, path: &[String]) {
+            self.samples += 1;
+            if let Some((first, rest)) = path.split_first() {
+                let idx = match self.children.iter().position(|c| &c.name == first) {
+                    Some(i) => i,
+                    None => {
+                        self.children.push(FlameNode {
+                            name: first.clone(),
+                            ..Default::default()
+                        });
+                        self.children.len() - 1
+                    }
+                };
+                self.children[idx].insert(rest);
+            }
+        }
+
+        fn depth(&self) -> usize {
+            self.children
+                .iter()
+                .map(|c| c.depth() + 1)
+                .max()
+                .unwrap_or(0)
+        }
+    }
+
+    fn function_name(pc: u64, symbols: &ElfSymbols, dwarf: &DwarfInfo) -> String {
+        match dwarf.function_at(pc) {
+            Some(f) => f.name.clone(),
+            None => symbols
+                .symbol_at(pc)
+                .map_or(format!("{:#010x}", pc), |(s, _)| s.name.clone()),
+        }
+    }
+
+    fn flame_color(name: &str) -> Color32 {
+        let h = name
+            .bytes()
+            .fold(0u32, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u32));
+        Color32::from_rgb(
+            205 + (h % 50) as u8,
+            80 + (h / 7 % 120) as u8,
+            40 + (h / 13 % 40) as u8,
+        )
+    }
+
+    #[derive(Default)]
+    pub struct ProfilerView {
+        method: SampleMethod,
+        b_call_stacks: bool,
+        duration_s: u64,
+        interval_ms: u64,
+        /// Set while sampling.
+        started: Option<Instant>,
+        last_sample: Option<Instant>,
+        core_idx: usize,
+        functions: HashMap<String, u64>,
+        n_samples: u64,
+        n_missed: u64,
+        flame: FlameNode,
+        rst_info: Option<String>,
+    }
+
+    impl ProfilerView {
+        fn start(&mut self, h: &mut ProbeRsHandler, core_idx: usize) {
+            if self.method == SampleMethod::Pcsr {
+                match h.enable_pc_sampling_register(core_idx) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        self.rst_info = Some(
+                            "DWT PC sampling needs a Cortex-M core, sample by halting instead"
+                                .to_owned(),
+                        );
+                        return;
+                    }
+                    Err(e) => {
+                        self.rst_info = Some(format!("{}", e));
+                        return;
+                    }
+                }
+            }
+            self.core_idx = core_idx;
+            self.functions.clear();
+            self.n_samples = 0;
+            self.n_missed = 0;
+            self.flame = FlameNode::default();
+            self.rst_info.take();
+            self.last_sample = None;
+            self.started = Some(Instant::now());
+        }
+
+        /// Takes one sample, returning the call path with the sampled function last.
+        fn sample(
+            &mut self,
+            h: &mut ProbeRsHandler,
+            symbols: &ElfSymbols,
+            dwarf: &DwarfInfo,
+        ) -> Result<Option<Vec<String>>, String> {
+            let core_idx = self.core_idx;
+            if self.method == SampleMethod::Pcsr {
+                let pc = h
+                    .read_pc_sampling_register(core_idx)
+                    .map_err(|e| format!("{}", e))?;
+                return Ok(pc.map(|pc| vec![function_name(pc as u64, symbols, dwarf)]));
+            }
+
+            // Only a running core gives a meaningful sample.
+            let status = h.core_status(core_idx).map_err(|e| format!("{}", e))?;
+            if status != Some(CoreStatus::Running) {
+                return Ok(None);
+            }
+            let timeout = Duration::from_millis(m_config::CORE_HALT_TIMEOUT_MS);
+            h.halt_core(core_idx, timeout)
+                .map_err(|e| format!("{}", e))?;
+            let path = if self.b_call_stacks {
+                unwind(h, core_idx, symbols, dwarf).map(|frames| {
+                    frames
+                        .iter()
+                        .rev()
+                        .map(|f| function_name(f.lookup_pc, symbols, dwarf))
+                        .collect()
+                })
+            } else {
+                h.read_pc(core_idx).map_err(|e| format!("{}", e)).map(|pc| {
+                    pc.map(|pc| vec![function_name(pc, symbols, dwarf)])
+                        .unwrap_or_default()
+                })
+            };
+            // Resume even when reading failed, the core was running before.
+            h.run_core(core_idx).map_err(|e| format!("{}", e))?;
+            let path = path?;
+            Ok(if path.is_empty() { None } else { Some(path) })
+        }
+
+        /// Samples for up to one frame's budget while profiling. Returns true while
+        /// profiling, so the caller keeps repainting.
+        pub fn poll(
+            &mut self,
+            h: &mut ProbeRsHandler,
+            symbols: &ElfSymbols,
+            dwarf: &DwarfInfo,
+        ) -> bool {
+            let Some(started) = self.started else {
+                return false;
+            };
+            if started.elapsed() >= Duration::from_secs(self.duration_s) {
+                self.started = None;
+                return false;
+            }
+            let interval = Duration::from_millis(self.interval_ms);
+            let budget = Duration::from_millis(m_config::PROFILER_FRAME_BUDGET_MS);
+            let frame_start = Instant::now();
+            while frame_start.elapsed() < budget {
+                if let Some(t) = self.last_sample {
+                    let since = t.elapsed();
+                    if since < interval {
+                        if frame_start.elapsed() + (interval - since) > budget {
+                            break;
+                        }
+                        std::thread::sleep(interval - since);
+                    }
+                }
+                self.last_sample = Some(Instant::now());
+                match self.sample(h, symbols, dwarf) {
+                    Ok(Some(path)) => {
+                        if let Some(leaf) = path.last() {
+                            *self.functions.entry(leaf.clone()).or_default() += 1;
+                        }
+                        self.flame.insert(&path);
+                        self.n_samples += 1;
+                    }
+                    Ok(None) => self.n_missed += 1,
+                    Err(e) => {
+                        self.rst_info = Some(e);
+                        self.started = None;
+                        return false;
+                    }
+                }
+            }
+            true
+        }
+
+        fn draw_flame_node(
+            &self,
+            painter: &eframe::egui::Painter,
+            node: &FlameNode,
+            min: Pos2,
+            width: f32,
+            pointer: Option<Pos2>,
+            hovered: &mut Option<String>,
+        ) {
+            let row_h = m_config::PROFILER_FLAME_ROW_HEIGHT;
+            let rect = Rect::from_min_size(min, vec2(width, row_h));
+            painter.rect_filled(rect.shrink(0.5), 2.0, flame_color(&node.name));
+            if width > 24.0 {
+                painter
+                    .with_clip_rect(rect.intersect(painter.clip_rect()))
+                    .text(
+                        rect.left_center() + vec2(3.0, 0.0),
+                        Align2::LEFT_CENTER,
+                        &node.name,
+                        FontId::monospace(11.0),
+                        Color32::BLACK,
+                    );
+            }
+            if pointer.is_some_and(|p| rect.contains(p)) {
+                *hovered = Some(format!(
+                    "{}\n{} samples ({:.1}%)",
+                    node.name,
+                    node.samples,
+                    node.samples as f64 * 100.0 / self.flame.samples as f64
+                ));
+            }
+            let mut x = min.x;
+            for c in node.children.iter() {
+                let w = width * c.samples as f32 / node.samples as f32;
+                self.draw_flame_node(painter, c, pos2(x, min.y + row_h), w, pointer, hovered);
+                x += w;
+            }
+        }
+
+        fn flame_ui(&self, ui: &mut eframe::egui::Ui) {
+            let depth = self.flame.depth();
+            if depth == 0 {
+                return;
+            }
+            let width = ui.available_width();
+            let (rect, resp) = ui.allocate_exact_size(
+                vec2(width, depth as f32 * m_config::PROFILER_FLAME_ROW_HEIGHT),
+                Sense::hover(),
+            );
+            let painter = ui.painter_at(rect);
+            let pointer = resp.hover_pos();
+            let mut hovered = None;
+            let mut x = rect.left();
+            for c in self.flame.children.iter() {
+                let w = width * c.samples as f32 / self.flame.samples as f32;
+                self.draw_flame_node(&painter, c, pos2(x, rect.top()), w, pointer, &mut hovered);
+                x += w;
+            }
+            if let Some(text) = hovered {
+                resp.on_hover_text(text);
+            }
+        }
+
+        pub fn ui(&mut self, ui: &mut eframe::egui::Ui, h: &mut ProbeRsHandler, core_idx: usize) {
+            if self.duration_s == 0 {
+                self.duration_s = m_config::PROFILER_DEFAULT_DURATION_S;
+            }
+            if self.interval_ms == 0 {
+                self.interval_ms = m_config::PROFILER_DEFAULT_INTERVAL_MS;
+            }
+            let b_running = self.started.is_some();
+
+            ui.horizontal(|ui| {
+                ui.add_enabled_ui(!b_running, |ui| {
+                    ui.selectable_value(&mut self.method, SampleMethod::Pcsr, "DWT PCSR");
+                    ui.selectable_value(&mut self.method, SampleMethod::Halt, "halt and read");
+                    ui.add_enabled(
+                        self.method == SampleMethod::Halt,
+                        eframe::egui::Checkbox::new(&mut self.b_call_stacks, "call stacks"),
+                    );
+                    ui.label("duration");
+                    ui.add(
+                        eframe::egui::DragValue::new(&mut self.duration_s)
+                            .range(1..=3600)
+                            .suffix(" s"),
+                    );
+                    ui.label("interval");
+                    ui.add(
+                        eframe::egui::DragValue::new(&mut self.interval_ms)
+                            .range(1..=1000)
+                            .suffix(" ms"),
+                    );
+                });
+                if !b_running {
+                    if ui.button("start").clicked() {
+                        self.start(h, core_idx);
+                    }
+                } else if ui.button("stop").clicked() {
+                    self.started = None;
+                }
+            });
+            let left = self
+                .started
+                .map_or(0, |t| self.duration_s.saturating_sub(t.elapsed().as_secs()));
+            ui.label(format!(
+                "core {}: {} samples, {} missed{}",
+                self.core_idx,
+                self.n_samples,
+                self.n_missed,
+                if b_running {
+                    format!(", {} s left", left)
+                } else {
+                    "".to_owned()
+                }
+            ));
+            if let Some(info) = self.rst_info.as_ref() {
+                ui.label(info);
+            }
+
+            ui.separator();
+            self.flame_ui(ui);
+            ui.separator();
+
+            let mut rows: Vec<(&String, &u64)> = self.functions.iter().collect();
+            rows.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+            eframe::egui::ScrollArea::vertical()
+                .auto_shrink(false)
+                .show(ui, |ui| {
+                    eframe::egui::Grid::new("debug_profiler")
+                        .striped(true)
+                        .num_columns(3)
+                        .show(ui, |ui| {
+                            ui.strong("function");
+                            ui.strong("samples");
+                            ui.strong("%");
+                            ui.end_row();
+                            for (name, n) in rows.into_iter().take(m_config::PROFILER_ROWS_SHOWN) {
+                                ui.monospace(name);
+                                ui.label(format!("{}", n));
+                                ui.label(format!(
+                                    "{:.1}",
+                                    *n as f64 * 100.0 / self.n_samples as f64
+                                ));
+                                ui.end_row();
+                            }
+                        });
+                });
+        }
+    }
+}
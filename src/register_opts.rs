@@ -0,0 +1,187 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_register_opts {
+    use crate::debug_opts::m_debug_opts::parse_number;
+    use crate::probe_rs_invoke::probe_rs_integration::ProbeRsHandler;
+    use probe_rs::{CoreRegister, CoreStatus, RegisterRole};
+
+    const HIGHLIGHTED_ROLES: [RegisterRole; 4] = [
+        RegisterRole::ProgramCounter,
+        RegisterRole::StackPointer,
+        RegisterRole::ReturnAddress,
+        RegisterRole::ProcessorStatus,
+    ];
+
+    struct RegisterRow {
+        reg: &'static CoreRegister,
+        value: Option<u128>,
+        last_halt_value: Option<u128>,
+    }
+
+    #[derive(Default)]
+    pub struct RegisterView {
+        rows: Vec<RegisterRow>,
+        b_decimal: bool,
+        b_was_halted: bool,
+        editing: Option<(usize, String)>,
+        rst_info: Option<String>,
+    }
+
+    impl RegisterView {
+        fn refresh(&mut self, h: &mut ProbeRsHandler, core_idx: usize, b_new_halt: bool) {
+            match h.read_core_registers(core_idx) {
+                Ok(regs) => {
+                    let rows = regs
+                        .into_iter()
+                        .map(|(reg, value)| {
+                            let old = self.rows.iter().find(|r| r.reg.id() == reg.id());
+                            // Compare against the values seen at the previous halt, so
+                            // edits and manual refreshes don't hide what the code changed.
+                            let last_halt_value = match old {
+                                Some(o) if b_new_halt => o.value,
+                                Some(o) => o.last_halt_value,
+                                None => None,
+                            };
+                            RegisterRow {
+                                reg,
+                                value,
+                                last_halt_value,
+                            }
+                        })
+                        .collect();
+                    self.rows = rows;
+                    self.rst_info.take();
+                }
+                Err(e) => {
+                    self.rst_info = Some(format!("{}", e));
+                }
+            }
+        }
+
+        fn format_value(&self, row: &RegisterRow) -> String {
+            match row.value {
+                Some(v) if self.b_decimal => format!("{}", v),
+                Some(v) => format!("{:#0width$x}", v, width = row.reg.size_in_bytes() * 2 + 2),
+                None => "-".to_owned(),
+            }
+        }
+
+        pub fn ui(
+            &mut self,
+            ui: &mut eframe::egui::Ui,
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            status: Option<CoreStatus>,
+        ) {
+            let b_halted = status.is_some_and(|s| s.is_halted());
+            if b_halted && !self.b_was_halted {
+                self.refresh(h, core_idx, true);
+            }
+            self.b_was_halted = b_halted;
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(b_halted, eframe::egui::Button::new("refresh"))
+                    .clicked()
+                {
+                    self.refresh(h, core_idx, false);
+                }
+                ui.checkbox(&mut self.b_decimal, "decimal");
+                if !b_halted {
+                    ui.label("halt the core to read or edit registers");
+                }
+            });
+
+            let mut write_req = None;
+            eframe::egui::ScrollArea::vertical()
+                .auto_shrink(false)
+                .show(ui, |ui| {
+                    eframe::egui::Grid::new("debug_registers")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (i, row) in self.rows.iter().enumerate() {
+                                let name = eframe::egui::RichText::new(row.reg.name());
+                                if HIGHLIGHTED_ROLES
+                                    .iter()
+                                    .any(|r| row.reg.register_has_role(*r))
+                                {
+                                    ui.label(name.strong().color(ui.visuals().hyperlink_color));
+                                } else {
+                                    ui.label(name);
+                                }
+
+                                let b_editing = self.editing.as_ref().is_some_and(|e| e.0 == i);
+                                if b_editing {
+                                    if let Some((_, text)) = self.editing.as_mut() {
+                                        let resp = ui.add(
+                                            eframe::egui::TextEdit::singleline(text)
+                                                .desired_width(160.0),
+                                        );
+                                        if resp.lost_focus() {
+                                            if ui.input(|i| i.key_pressed(eframe::egui::Key::Enter))
+                                            {
+                                                write_req = Some((i, text.clone()));
+                                            }
+                                            self.editing = None;
+                                        } else {
+                                            resp.request_focus();
+                                        }
+                                    }
+                                } else {
+                                    let mut text =
+                                        eframe::egui::RichText::new(self.format_value(row))
+                                            .monospace();
+                                    if row.last_halt_value.is_some()
+                                        && row.last_halt_value != row.value
+                                    {
+                                        text = text.color(ui.visuals().warn_fg_color);
+                                    }
+                                    let resp = ui
+                                        .add(
+                                            eframe::egui::Label::new(text)
+                                                .sense(eframe::egui::Sense::click()),
+                                        )
+                                        .on_hover_text("double click to edit");
+                                    if b_halted && resp.double_clicked() {
+                                        self.editing = Some((i, self.format_value(row)));
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+
+            if let Some((i, text)) = write_req {
+                if let Some(row) = self.rows.get(i) {
+                    match parse_number(&text) {
+                        Some(v) => match h.write_core_register(core_idx, row.reg, v) {
+                            Ok(_) => self.refresh(h, core_idx, false),
+                            Err(e) => self.rst_info = Some(format!("{}", e)),
+                        },
+                        None => self.rst_info = Some(format!("invalid value: {}", text)),
+                    }
+                }
+            }
+
+            if let Some(info) = self.rst_info.as_ref() {
+                ui.label(info);
+            }
+        }
+    }
+}
@@ -0,0 +1,456 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+/// A small RISC-V disassembler for the RV32/RV64 IMAC, Zicsr and Zifencei
+/// instructions found on microcontrollers. capstone 0.8 has no RISC-V support.
+pub mod m_riscv_disasm {
+    const REG_NAMES: [&str; 32] = [
+        "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2", "a3", "a4",
+        "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4",
+        "t5", "t6",
+    ];
+
+    const CSR_NAMES: [(u32, &str); 17] = [
+        (0x001, "fflags"),
+        (0x002, "frm"),
+        (0x003, "fcsr"),
+        (0x300, "mstatus"),
+        (0x301, "misa"),
+        (0x304, "mie"),
+        (0x305, "mtvec"),
+        (0x340, "mscratch"),
+        (0x341, "mepc"),
+        (0x342, "mcause"),
+        (0x343, "mtval"),
+        (0x344, "mip"),
+        (0xB00, "mcycle"),
+        (0xC00, "cycle"),
+        (0xC01, "time"),
+        (0xC02, "instret"),
+        (0xF14, "mhartid"),
+    ];
+
+    fn reg(n: u32) -> &'static str {
+        REG_NAMES[(n & 31) as usize]
+    }
+
+    /// Registers x8-x15, as addressed by the 3-bit fields of compressed instructions.
+    fn creg(n: u32) -> &'static str {
+        reg(8 + (n & 7))
+    }
+
+    fn csr(n: u32) -> String {
+        CSR_NAMES
+            .iter()
+            .find(|(c, _)| *c == n)
+            .map_or(format!("{:#x}", n), |(_, name)| (*name).to_owned())
+    }
+
+    fn bits(v: u32, hi: u32, lo: u32) -> u32 {
+        (v >> lo) & ((1 << (hi - lo + 1)) - 1)
+    }
+
+    fn sext(v: u32, width: u32) -> i64 {
+        let shift = 64 - width;
+        ((v as i64) << shift) >> shift
+    }
+
+    fn target(address: u64, offset: i64) -> String {
+        format!("{:#x}", address.wrapping_add(offset as u64))
+    }
+
+    /// Decodes the instruction at the start of `bytes`. Returns its length in bytes
+    /// and its text, or None if `bytes` is too short.
+    pub fn decode(bytes: &[u8], address: u64, b_rv64: bool) -> Option<(usize, String)> {
+        let lo = u16::from_le_bytes([*bytes.first()?, *bytes.get(1)?]) as u32;
+        if lo & 3 != 3 {
+            return Some((2, decode_compressed(lo, address, b_rv64)));
+        }
+        let hi = u16::from_le_bytes([*bytes.get(2)?, *bytes.get(3)?]) as u32;
+        Some((4, decode_32(lo | (hi << 16), address, b_rv64)))
+    }
+
+    fn decode_32(w: u32, address: u64, b_rv64: bool) -> String {
+        let rd = bits(w, 11, 7);
+        let rs1 = bits(w, 19, 15);
+        let rs2 = bits(w, 24, 20);
+        let funct3 = bits(w, 14, 12);
+        let funct7 = bits(w, 31, 25);
+        let imm_i = sext(bits(w, 31, 20), 12);
+        let imm_s = sext((bits(w, 31, 25) << 5) | rd, 12);
+        let unknown = format!(".word {:#010x}", w);
+
+        match w & 0x7F {
+            0x37 => format!("lui {}, {:#x}", reg(rd), w >> 12),
+            0x17 => format!("auipc {}, {:#x}", reg(rd), w >> 12),
+            0x6F => {
+                let imm = (bits(w, 31, 31) << 20)
+                    | (bits(w, 19, 12) << 12)
+                    | (bits(w, 20, 20) << 11)
+                    | (bits(w, 30, 21) << 1);
+                let t = target(address, sext(imm, 21));
+                match rd {
+                    0 => format!("j {}", t),
+                    1 => format!("jal {}", t),
+                    _ => format!("jal {}, {}", reg(rd), t),
+                }
+            }
+            0x67 if funct3 == 0 => match (rd, rs1, imm_i) {
+                (0, 1, 0) => "ret".to_owned(),
+                (0, _, 0) => format!("jr {}", reg(rs1)),
+                (1, _, 0) => format!("jalr {}", reg(rs1)),
+                _ => format!("jalr {}, {}({})", reg(rd), imm_i, reg(rs1)),
+            },
+            0x63 => {
+                let imm = (bits(w, 31, 31) << 12)
+                    | (bits(w, 7, 7) << 11)
+                    | (bits(w, 30, 25) << 5)
+                    | (bits(w, 11, 8) << 1);
+                let t = target(address, sext(imm, 13));
+                let op = match funct3 {
+                    0 => "beq",
+                    1 => "bne",
+                    4 => "blt",
+                    5 => "bge",
+                    6 => "bltu",
+                    7 => "bgeu",
+                    _ => return unknown,
+                };
+                if rs2 == 0 && funct3 < 6 {
+                    format!("{}z {}, {}", op, reg(rs1), t)
+                } else {
+                    format!("{} {}, {}, {}", op, reg(rs1), reg(rs2), t)
+                }
+            }
+            0x03 => {
+                let op = match funct3 {
+                    0 => "lb",
+                    1 => "lh",
+                    2 => "lw",
+                    3 if b_rv64 => "ld",
+                    4 => "lbu",
+                    5 => "lhu",
+                    6 if b_rv64 => "lwu",
+                    _ => return unknown,
+                };
+                format!("{} {}, {}({})", op, reg(rd), imm_i, reg(rs1))
+            }
+            0x07 if matches!(funct3, 2 | 3) => format!(
+                "{} f{}, {}({})",
+                if funct3 == 2 { "flw" } else { "fld" },
+                rd,
+                imm_i,
+                reg(rs1)
+            ),
+            0x23 => {
+                let op = match funct3 {
+                    0 => "sb",
+                    1 => "sh",
+                    2 => "sw",
+                    3 if b_rv64 => "sd",
+                    _ => return unknown,
+                };
+                format!("{} {}, {}({})", op, reg(rs2), imm_s, reg(rs1))
+            }
+            0x27 if matches!(funct3, 2 | 3) => format!(
+                "{} f{}, {}({})",
+                if funct3 == 2 { "fsw" } else { "fsd" },
+                rs2,
+                imm_s,
+                reg(rs1)
+            ),
+            0x13 => {
+                let shamt = bits(w, if b_rv64 { 25 } else { 24 }, 20);
+                match funct3 {
+                    0 if w == 0x13 => "nop".to_owned(),
+                    0 if rs1 == 0 => format!("li {}, {}", reg(rd), imm_i),
+                    0 if imm_i == 0 => format!("mv {}, {}", reg(rd), reg(rs1)),
+                    0 => format!("addi {}, {}, {}", reg(rd), reg(rs1), imm_i),
+                    1 => format!("slli {}, {}, {}", reg(rd), reg(rs1), shamt),
+                    2 => format!("slti {}, {}, {}", reg(rd), reg(rs1), imm_i),
+                    3 => format!("sltiu {}, {}, {}", reg(rd), reg(rs1), imm_i),
+                    4 if imm_i == -1 => format!("not {}, {}", reg(rd), reg(rs1)),
+                    4 => format!("xori {}, {}, {}", reg(rd), reg(rs1), imm_i),
+                    5 => format!(
+                        "{} {}, {}, {}",
+                        if bits(w, 30, 30) == 1 { "srai" } else { "srli" },
+                        reg(rd),
+                        reg(rs1),
+                        shamt
+                    ),
+                    6 => format!("ori {}, {}, {}", reg(rd), reg(rs1), imm_i),
+                    _ => format!("andi {}, {}, {}", reg(rd), reg(rs1), imm_i),
+                }
+            }
+            0x1B if b_rv64 => match funct3 {
+                0 if imm_i == 0 => format!("sext.w {}, {}", reg(rd), reg(rs1)),
+                0 => format!("addiw {}, {}, {}", reg(rd), reg(rs1), imm_i),
+                1 => format!("slliw {}, {}, {}", reg(rd), reg(rs1), rs2),
+                5 => format!(
+                    "{} {}, {}, {}",
+                    if bits(w, 30, 30) == 1 {
+                        "sraiw"
+                    } else {
+                        "srliw"
+                    },
+                    reg(rd),
+                    reg(rs1),
+                    rs2
+                ),
+                _ => unknown,
+            },
+            0x33 => {
+                let op = match (funct7, funct3) {
+                    (0x00, 0) => "add",
+                    (0x20, 0) => "sub",
+                    (0x00, 1) => "sll",
+                    (0x00, 2) => "slt",
+                    (0x00, 3) => "sltu",
+                    (0x00, 4) => "xor",
+                    (0x00, 5) => "srl",
+                    (0x20, 5) => "sra",
+                    (0x00, 6) => "or",
+                    (0x00, 7) => "and",
+                    (0x01, 0) => "mul",
+                    (0x01, 1) => "mulh",
+                    (0x01, 2) => "mulhsu",
+                    (0x01, 3) => "mulhu",
+                    (0x01, 4) => "div",
+                    (0x01, 5) => "divu",
+                    (0x01, 6) => "rem",
+                    (0x01, 7) => "remu",
+                    _ => return unknown,
+                };
+                if op == "sub" && rs1 == 0 {
+                    return format!("neg {}, {}", reg(rd), reg(rs2));
+                }
+                format!("{} {}, {}, {}", op, reg(rd), reg(rs1), reg(rs2))
+            }
+            0x3B if b_rv64 => {
+                let op = match (funct7, funct3) {
+                    (0x00, 0) => "addw",
+                    (0x20, 0) => "subw",
+                    (0x00, 1) => "sllw",
+                    (0x00, 5) => "srlw",
+                    (0x20, 5) => "sraw",
+                    (0x01, 0) => "mulw",
+                    (0x01, 4) => "divw",
+                    (0x01, 5) => "divuw",
+                    (0x01, 6) => "remw",
+                    (0x01, 7) => "remuw",
+                    _ => return unknown,
+                };
+                format!("{} {}, {}, {}", op, reg(rd), reg(rs1), reg(rs2))
+            }
+            0x2F if matches!(funct3, 2 | 3) => {
+                let width = if funct3 == 2 { "w" } else { "d" };
+                let op = match bits(w, 31, 27) {
+                    0x02 => return format!("lr.{} {}, ({})", width, reg(rd), reg(rs1)),
+                    0x03 => "sc",
+                    0x01 => "amoswap",
+                    0x00 => "amoadd",
+                    0x04 => "amoxor",
+                    0x0C => "amoand",
+                    0x08 => "amoor",
+                    0x10 => "amomin",
+                    0x14 => "amomax",
+                    0x18 => "amominu",
+                    0x1C => "amomaxu",
+                    _ => return unknown,
+                };
+                format!("{}.{} {}, {}, ({})", op, width, reg(rd), reg(rs2), reg(rs1))
+            }
+            0x0F => match funct3 {
+                0 => "fence".to_owned(),
+                1 => "fence.i".to_owned(),
+                _ => unknown,
+            },
+            0x73 => {
+                let c = bits(w, 31, 20);
+                match funct3 {
+                    0 => match w {
+                        0x0000_0073 => "ecall".to_owned(),
+                        0x0010_0073 => "ebreak".to_owned(),
+                        0x1020_0073 => "sret".to_owned(),
+                        0x3020_0073 => "mret".to_owned(),
+                        0x1050_0073 => "wfi".to_owned(),
+                        _ => unknown,
+                    },
+                    2 if rs1 == 0 => format!("csrr {}, {}", reg(rd), csr(c)),
+                    1 if rd == 0 => format!("csrw {}, {}", csr(c), reg(rs1)),
+                    1..=3 => format!(
+                        "{} {}, {}, {}",
+                        ["csrrw", "csrrs", "csrrc"][funct3 as usize - 1],
+                        reg(rd),
+                        csr(c),
+                        reg(rs1)
+                    ),
+                    5..=7 => format!(
+                        "{} {}, {}, {}",
+                        ["csrrwi", "csrrsi", "csrrci"][funct3 as usize - 5],
+                        reg(rd),
+                        csr(c),
+                        rs1
+                    ),
+                    _ => unknown,
+                }
+            }
+            _ => unknown,
+        }
+    }
+
+    fn decode_compressed(h: u32, address: u64, b_rv64: bool) -> String {
+        let unknown = format!(".half {:#06x}", h);
+        if h == 0 {
+            return "unimp".to_owned();
+        }
+        let funct3 = bits(h, 15, 13);
+        let rd = bits(h, 11, 7);
+        let rs2 = bits(h, 6, 2);
+        let imm6 = sext((bits(h, 12, 12) << 5) | rs2, 6);
+        // Offsets of c.lw/c.sw and c.ld/c.sd.
+        let off_w = (bits(h, 12, 10) << 3) | (bits(h, 6, 6) << 2) | (bits(h, 5, 5) << 6);
+        let off_d = (bits(h, 12, 10) << 3) | (bits(h, 6, 5) << 6);
+
+        match (h & 3, funct3) {
+            (0, 0) => {
+                let imm = (bits(h, 12, 11) << 4)
+                    | (bits(h, 10, 7) << 6)
+                    | (bits(h, 6, 6) << 2)
+                    | (bits(h, 5, 5) << 3);
+                format!("addi {}, sp, {}", creg(h >> 2), imm)
+            }
+            (0, 1) => format!("fld f{}, {}({})", 8 + bits(h, 4, 2), off_d, creg(h >> 7)),
+            (0, 2) => format!("lw {}, {}({})", creg(h >> 2), off_w, creg(h >> 7)),
+            (0, 3) if b_rv64 => format!("ld {}, {}({})", creg(h >> 2), off_d, creg(h >> 7)),
+            (0, 3) => format!("flw f{}, {}({})", 8 + bits(h, 4, 2), off_w, creg(h >> 7)),
+            (0, 5) => format!("fsd f{}, {}({})", 8 + bits(h, 4, 2), off_d, creg(h >> 7)),
+            (0, 6) => format!("sw {}, {}({})", creg(h >> 2), off_w, creg(h >> 7)),
+            (0, 7) if b_rv64 => format!("sd {}, {}({})", creg(h >> 2), off_d, creg(h >> 7)),
+            (0, 7) => format!("fsw f{}, {}({})", 8 + bits(h, 4, 2), off_w, creg(h >> 7)),
+            (1, 0) if rd == 0 => "nop".to_owned(),
+            (1, 0) => format!("addi {}, {}, {}", reg(rd), reg(rd), imm6),
+            (1, 1) if b_rv64 => format!("addiw {}, {}, {}", reg(rd), reg(rd), imm6),
+            (1, 1) | (1, 5) => {
+                let imm = (bits(h, 12, 12) << 11)
+                    | (bits(h, 11, 11) << 4)
+                    | (bits(h, 10, 9) << 8)
+                    | (bits(h, 8, 8) << 10)
+                    | (bits(h, 7, 7) << 6)
+                    | (bits(h, 6, 6) << 7)
+                    | (bits(h, 5, 3) << 1)
+                    | (bits(h, 2, 2) << 5);
+                let t = target(address, sext(imm, 12));
+                if funct3 == 1 {
+                    format!("jal {}", t)
+                } else {
+                    format!("j {}", t)
+                }
+            }
+            (1, 2) => format!("li {}, {}", reg(rd), imm6),
+            (1, 3) if rd == 2 => {
+                let imm = (bits(h, 12, 12) << 9)
+                    | (bits(h, 6, 6) << 4)
+                    | (bits(h, 5, 5) << 6)
+                    | (bits(h, 4, 3) << 7)
+                    | (bits(h, 2, 2) << 5);
+                format!("addi sp, sp, {}", sext(imm, 10))
+            }
+            (1, 3) => format!("lui {}, {:#x}", reg(rd), (imm6 as u32) & 0xF_FFFF),
+            (1, 4) => {
+                let r = creg(h >> 7);
+                let shamt = (bits(h, 12, 12) << 5) | rs2;
+                match (bits(h, 11, 10), bits(h, 12, 12), bits(h, 6, 5)) {
+                    (0, _, _) => format!("srli {}, {}, {}", r, r, shamt),
+                    (1, _, _) => format!("srai {}, {}, {}", r, r, shamt),
+                    (2, _, _) => format!("andi {}, {}, {}", r, r, imm6),
+                    (_, 0, op) => format!(
+                        "{} {}, {}, {}",
+                        ["sub", "xor", "or", "and"][op as usize],
+                        r,
+                        r,
+                        creg(h >> 2)
+                    ),
+                    (_, _, 0) if b_rv64 => format!("subw {}, {}, {}", r, r, creg(h >> 2)),
+                    (_, _, 1) if b_rv64 => format!("addw {}, {}, {}", r, r, creg(h >> 2)),
+                    _ => unknown,
+                }
+            }
+            (1, _) => {
+                let imm = (bits(h, 12, 12) << 8)
+                    | (bits(h, 11, 10) << 3)
+                    | (bits(h, 6, 5) << 6)
+                    | (bits(h, 4, 3) << 1)
+                    | (bits(h, 2, 2) << 5);
+                format!(
+                    "{} {}, {}",
+                    if funct3 == 6 { "beqz" } else { "bnez" },
+                    creg(h >> 7),
+                    target(address, sext(imm, 9))
+                )
+            }
+            (2, 0) => format!(
+                "slli {}, {}, {}",
+                reg(rd),
+                reg(rd),
+                (bits(h, 12, 12) << 5) | rs2
+            ),
+            (2, 1) | (2, 3) if funct3 == 1 || b_rv64 => {
+                let off = (bits(h, 12, 12) << 5) | (bits(h, 6, 5) << 3) | (bits(h, 4, 2) << 6);
+                if funct3 == 1 {
+                    format!("fld f{}, {}(sp)", rd, off)
+                } else {
+                    format!("ld {}, {}(sp)", reg(rd), off)
+                }
+            }
+            (2, 2) | (2, 3) => {
+                let off = (bits(h, 12, 12) << 5) | (bits(h, 6, 4) << 2) | (bits(h, 3, 2) << 6);
+                if funct3 == 2 {
+                    format!("lw {}, {}(sp)", reg(rd), off)
+                } else {
+                    format!("flw f{}, {}(sp)", rd, off)
+                }
+            }
+            (2, 4) => match (bits(h, 12, 12), rd, rs2) {
+                (0, 1, 0) => "ret".to_owned(),
+                (0, _, 0) => format!("jr {}", reg(rd)),
+                (0, _, _) => format!("mv {}, {}", reg(rd), reg(rs2)),
+                (_, 0, 0) => "ebreak".to_owned(),
+                (_, _, 0) => format!("jalr {}", reg(rd)),
+                _ => format!("add {}, {}, {}", reg(rd), reg(rd), reg(rs2)),
+            },
+            (2, 5) | (2, 7) if funct3 == 5 || b_rv64 => {
+                let off = (bits(h, 12, 10) << 3) | (bits(h, 9, 7) << 6);
+                if funct3 == 5 {
+                    format!("fsd f{}, {}(sp)", rs2, off)
+                } else {
+                    format!("sd {}, {}(sp)", reg(rs2), off)
+                }
+            }
+            (2, _) => {
+                let off = (bits(h, 12, 9) << 2) | (bits(h, 8, 7) << 6);
+                if funct3 == 6 {
+                    format!("sw {}, {}(sp)", reg(rs2), off)
+                } else {
+                    format!("fsw f{}, {}(sp)", rs2, off)
+                }
+            }
+            _ => unknown,
+        }
+    }
+}
@@ -17,22 +17,166 @@
  */
 
 pub mod m_rtt_opts {
+    use crate::chip_info_opts::m_chip_info_opts::ChipInfoView;
+    use crate::configuration::m_config;
+    use crate::gdb_server::m_gdb_server::GdbServer;
+    use crate::probe_opts::m_probe_opts::{chip_detection_ui, probe_config_ui, unlock_ui};
     use crate::probe_rs_invoke::probe_rs_integration::ProbeRsHandler;
-    use chrono::Local;
+    use crate::rtt_trigger::m_rtt_trigger::RttTriggers;
+    use chrono::{DateTime, Local};
     use egui_file::FileDialog;
-    use probe_rs::rtt::ScanRegion;
+    use probe_rs::rtt::{ChannelMode, ScanRegion};
     use std::{
         borrow::{Borrow, BorrowMut},
-        collections::VecDeque,
+        collections::{HashMap, VecDeque},
+        error::Error,
+        fs, io,
+        io::Write,
         path::PathBuf,
-        time::Duration,
+        time::{Duration, Instant},
     };
 
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum RttAttachStrategy {
+        Scan,
+        Region,
+        RegionRetry(Duration),
+    }
+
+    const CHANNEL_MODES: [(ChannelMode, &str); 3] = [
+        (ChannelMode::NoBlockSkip, "non-blocking, skip"),
+        (ChannelMode::NoBlockTrim, "non-blocking, trim"),
+        (ChannelMode::BlockIfFull, "block if full"),
+    ];
+
+    #[derive(Default)]
+    struct RttChannelStats {
+        total_bytes: u64,
+        bytes_per_sec: f64,
+        overflows: u64,
+        window_start: Option<Instant>,
+        window_bytes: u64,
+    }
+
+    impl RttChannelStats {
+        fn record(&mut self, n_drained: usize, target_buf_size: usize) {
+            self.total_bytes += n_drained as u64;
+            self.window_bytes += n_drained as u64;
+            // A full target buffer at poll time means the firmware had no room left and
+            // (unless blocking) dropped whatever it tried to write in the meantime.
+            if target_buf_size > 1 && n_drained >= target_buf_size - 1 {
+                self.overflows += 1;
+            }
+            let window_start = *self.window_start.get_or_insert_with(Instant::now);
+            let elapsed = window_start.elapsed().as_secs_f64();
+            if elapsed >= 1.0 {
+                self.bytes_per_sec = self.window_bytes as f64 / elapsed;
+                self.window_bytes = 0;
+                self.window_start = Some(Instant::now());
+            }
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    enum TimestampMode {
+        #[default]
+        WallClock,
+        SinceAttach,
+        SincePrevious,
+        Target,
+        Hidden,
+    }
+
+    const TIMESTAMP_MODES: [(TimestampMode, &str); 5] = [
+        (TimestampMode::WallClock, "wall clock"),
+        (TimestampMode::SinceAttach, "since attach"),
+        (TimestampMode::SincePrevious, "since previous line"),
+        (TimestampMode::Target, "target prefix"),
+        (TimestampMode::Hidden, "hidden"),
+    ];
+
+    struct RttLine {
+        seq: usize,
+        channel: usize,
+        host_time: DateTime<Local>,
+        since_attach: Duration,
+        since_prev: Duration,
+        text: String,
+    }
+
+    /// Splits a leading target-side timestamp such as `[  12.345678]`, `<1234ms>`,
+    /// `12:34:56.789 ` or `00012345:` off an RTT line.
+    fn split_target_timestamp(text: &str) -> Option<(&str, &str)> {
+        let t = text.trim_start();
+        let (close, body) = match t.chars().next()? {
+            '[' => (Some(']'), t[1..].trim_start()),
+            '(' => (Some(')'), t[1..].trim_start()),
+            '<' => (Some('>'), t[1..].trim_start()),
+            _ => (None, t),
+        };
+        if !body.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        let n_end = body
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ':'))
+            .unwrap_or(body.len());
+        let unit_end = body[n_end..]
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .map_or(body.len(), |i| n_end + i);
+        let (stamp, rest) = body.split_at(unit_end);
+        let rest = match close {
+            Some(c) => rest.trim_start().strip_prefix(c)?,
+            None => {
+                if !(stamp.ends_with(':') || rest.starts_with(' ')) {
+                    return None;
+                }
+                rest
+            }
+        };
+        Some((stamp.trim_end_matches(':'), rest.trim_start()))
+    }
+
+    fn format_rtt_line(line: &RttLine, mode: TimestampMode, b_hide_seq_num: bool) -> String {
+        let mut text = String::new();
+        if !b_hide_seq_num {
+            text.push_str(&format!("{}: ", line.seq));
+        }
+        match mode {
+            TimestampMode::WallClock => {
+                let ymdhms = line.host_time.format("%Y-%m-%d %H:%M:%S%.3f");
+                text.push_str(&format!("{} {}", ymdhms, line.text));
+            }
+            TimestampMode::SinceAttach => {
+                let secs = line.since_attach.as_secs_f64();
+                text.push_str(&format!("{:>12.6} {}", secs, line.text));
+            }
+            TimestampMode::SincePrevious => {
+                let secs = line.since_prev.as_secs_f64();
+                text.push_str(&format!("+{:.6} {}", secs, line.text));
+            }
+            TimestampMode::Target => match split_target_timestamp(&line.text) {
+                Some((stamp, rest)) => text.push_str(&format!("[{}] {}", stamp, rest)),
+                None => text.push_str(&format!("[-] {}", line.text)),
+            },
+            TimestampMode::Hidden => text.push_str(&line.text),
+        }
+        text
+    }
+
+    fn channel_mode_label(mode: Option<ChannelMode>) -> &'static str {
+        CHANNEL_MODES
+            .iter()
+            .find(|(m, _)| Some(*m) == mode)
+            .map_or("unknown", |(_, label)| label)
+    }
+
     #[derive(Default)]
     pub struct Rttio {
         probe_selected_idx: usize,
         probe_rs_handler: Option<ProbeRsHandler>,
         target_chip_name: String,
+        b_unlock_confirmed: bool,
+        chip_info: ChipInfoView,
         b_try_to_read: bool,
         cur_target_core_idx: usize,
         cur_target_channel_idx: usize,
@@ -40,13 +184,288 @@ pub mod m_rtt_opts {
         file_dialog: Option<FileDialog>,
         selected_file: Option<PathBuf>,
         retry_rtt_attach_time_out: u64,
-        log_buf: VecDeque<String>,
-        n_display_row: usize,
+        log_buf: VecDeque<RttLine>,
         n_items: usize,
         filter_s: String,
+        attach_strategy: Option<RttAttachStrategy>,
+        b_auto_reattach: bool,
+        b_rtt_lost: bool,
+        last_rtt_check: Option<Instant>,
+        up_ch_modes: Vec<Option<ChannelMode>>,
+        forced_ch_modes: HashMap<usize, ChannelMode>,
+        b_refresh_ch_modes: bool,
+        rtt_poll_interval_ms: u64,
+        rtt_read_buf_size: usize,
+        last_rtt_poll: Option<Instant>,
+        rx_pending: Vec<u8>,
+        ch_stats: HashMap<usize, RttChannelStats>,
+        rtt_read_err: Option<String>,
+        ts_modes: HashMap<usize, TimestampMode>,
+        b_hide_seq_num: bool,
+        attach_time: Option<Instant>,
+        last_line_time: Option<Instant>,
+        triggers: RttTriggers,
+        gdb_server: GdbServer,
     }
 
     impl Rttio {
+        fn run_attach_strategy(
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            strategy: RttAttachStrategy,
+        ) -> Result<bool, Box<dyn Error>> {
+            match strategy {
+                RttAttachStrategy::Scan => {
+                    h.attach_rtt(core_idx)?;
+                }
+                RttAttachStrategy::Region => {
+                    h.attach_rtt_region(core_idx)?;
+                }
+                RttAttachStrategy::RegionRetry(time_out_duration) => {
+                    h.attach_retry_loop(core_idx, time_out_duration)?;
+                }
+            }
+            h.get_up_channels_size();
+            Ok(h.rtt.is_some())
+        }
+
+        fn push_log_line(&mut self, line: &str) {
+            let now = Instant::now();
+            let text = RttLine {
+                seq: self.n_items,
+                channel: self.cur_target_channel_idx,
+                host_time: Local::now(),
+                since_attach: self.attach_time.map_or(Duration::ZERO, |t| now - t),
+                since_prev: self.last_line_time.map_or(Duration::ZERO, |t| now - t),
+                text: line.to_owned(),
+            };
+            self.last_line_time = Some(now);
+            self.log_buf.push_back(text);
+            self.n_items = self.n_items.wrapping_add(1);
+            while self.log_buf.len() > m_config::RTT_LOG_HISTORY_LEN {
+                self.log_buf.pop_front();
+            }
+        }
+
+        /// Serves the GDB client from this page's session, also while another page
+        /// is shown.
+        pub fn poll_gdb(&mut self, ctx: &eframe::egui::Context) {
+            if self
+                .gdb_server
+                .poll(self.probe_rs_handler.as_mut(), self.cur_target_core_idx)
+            {
+                ctx.request_repaint_after(Duration::from_millis(m_config::GDB_POLL_MS));
+            }
+        }
+
+        fn push_rtt_output(&mut self, line: &str) {
+            self.push_log_line(line);
+            for idx in self.triggers.check(line) {
+                self.fire_trigger(idx, line);
+            }
+        }
+
+        fn fire_trigger(&mut self, idx: usize, line: &str) {
+            let Some(rule) = self.triggers.rule(idx) else {
+                return;
+            };
+            let (b_stop_polling, b_halt_core, b_snapshot, b_run_command) = (
+                rule.b_stop_polling,
+                rule.b_halt_core,
+                rule.b_snapshot,
+                rule.b_run_command,
+            );
+            let mut notes = Vec::new();
+            if b_stop_polling {
+                self.b_try_to_read = false;
+                notes.push("polling stopped".to_owned());
+            }
+            if b_halt_core {
+                if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                    let timeout = Duration::from_millis(m_config::CORE_HALT_TIMEOUT_MS);
+                    match h.halt_core(self.cur_target_core_idx, timeout) {
+                        Ok(_) => notes.push(format!("core {} halted", self.cur_target_core_idx)),
+                        Err(e) => notes.push(format!("halt failed: {}", e)),
+                    }
+                }
+            }
+            if b_snapshot {
+                match self.snapshot_log() {
+                    Ok(p) => notes.push(format!("log saved to {}", p.display())),
+                    Err(e) => notes.push(format!("snapshot failed: {}", e)),
+                }
+            }
+            if b_run_command {
+                match self.triggers.run_command(idx, line) {
+                    Ok(_) => notes.push("command started".to_owned()),
+                    Err(e) => notes.push(format!("command failed: {}", e)),
+                }
+            }
+            self.triggers.record(idx, line, notes);
+        }
+
+        fn snapshot_log(&self) -> Result<PathBuf, io::Error> {
+            let mut path = PathBuf::from(&self.triggers.snapshot_dir);
+            path.push(format!(
+                "rtt_snapshot_{}.log",
+                Local::now().format("%Y%m%d_%H%M%S%.3f")
+            ));
+            let mut file = fs::File::create(&path)?;
+            for l in self.log_buf.iter() {
+                let mode = self.ts_modes.get(&l.channel).copied().unwrap_or_default();
+                writeln!(file, "{}", format_rtt_line(l, mode, self.b_hide_seq_num))?;
+            }
+            Ok(path)
+        }
+
+        fn auto_reattach(&mut self, b_read_failed: bool) {
+            let Some(strategy) = self.attach_strategy else {
+                return;
+            };
+            if b_read_failed {
+                self.b_rtt_lost = true;
+            }
+            let check_interval = Duration::from_millis(m_config::RTT_REATTACH_CHECK_INTERVAL_MS);
+            if self
+                .last_rtt_check
+                .is_some_and(|t| t.elapsed() < check_interval)
+            {
+                return;
+            }
+            self.last_rtt_check = Some(Instant::now());
+
+            let mut b_reattached = false;
+            if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                if !self.b_rtt_lost {
+                    let b_reset = h
+                        .core_reset_detected(self.cur_target_core_idx)
+                        .unwrap_or(false);
+                    let b_valid = h
+                        .rtt_control_block_valid(self.cur_target_core_idx)
+                        .unwrap_or(false);
+                    self.b_rtt_lost = b_reset || !b_valid;
+                }
+                if self.b_rtt_lost {
+                    h.detach_rtt();
+                    // A single attempt per check interval, the retry loop would block
+                    // the UI for its whole timeout while the target is in reset.
+                    let strategy = match strategy {
+                        RttAttachStrategy::RegionRetry(_) => RttAttachStrategy::Region,
+                        s => s,
+                    };
+                    if let Ok(true) =
+                        Self::run_attach_strategy(h, self.cur_target_core_idx, strategy)
+                    {
+                        if self.cur_target_channel_idx >= h.up_chs_size {
+                            self.cur_target_channel_idx = 0;
+                        }
+                        self.b_rtt_lost = false;
+                        self.b_refresh_ch_modes = true;
+                        self.attach_time = Some(Instant::now());
+                        b_reattached = true;
+                    }
+                }
+            }
+            if b_reattached {
+                self.push_log_line(m_config::RTT_REATTACH_MARKER);
+            }
+        }
+
+        fn poll_rtt(&mut self) {
+            let mut buf = vec![0u8; self.rtt_read_buf_size];
+            let mut n_drained = 0;
+            let mut b_read_failed = false;
+            if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                let target_buf_size = h.rtt_up_channel_buffer_size(self.cur_target_channel_idx);
+                // Drain the target buffer instead of taking one chunk per poll.
+                for _ in 0..m_config::RTT_MAX_READS_PER_POLL {
+                    match h.rtt_read_from_channel(
+                        &mut buf,
+                        self.cur_target_core_idx,
+                        self.cur_target_channel_idx,
+                    ) {
+                        Ok(read_size) => {
+                            self.rx_pending.extend_from_slice(&buf[..read_size]);
+                            n_drained += read_size;
+                            self.rtt_read_err = None;
+                            if read_size < buf.len() {
+                                break;
+                            }
+                        }
+                        Err(e) => {
+                            b_read_failed = true;
+                            self.rtt_read_err = Some(format!("{:#?}", e));
+                            break;
+                        }
+                    }
+                }
+                self.ch_stats
+                    .entry(self.cur_target_channel_idx)
+                    .or_default()
+                    .record(n_drained, target_buf_size);
+            }
+
+            while let Some(pos) = self.rx_pending.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = self.rx_pending.drain(..=pos).collect();
+                let text = String::from_utf8_lossy(&line);
+                self.push_rtt_output(text.trim_end_matches(['\r', '\n']));
+            }
+            // Flush output without a line ending once the target stops sending.
+            if !self.rx_pending.is_empty()
+                && (n_drained == 0 || self.rx_pending.len() >= self.rtt_read_buf_size)
+            {
+                let line = std::mem::take(&mut self.rx_pending);
+                self.push_rtt_output(&String::from_utf8_lossy(&line));
+            }
+
+            if self.b_auto_reattach {
+                self.auto_reattach(b_read_failed);
+            }
+        }
+
+        fn refresh_channel_modes(&mut self) {
+            self.b_refresh_ch_modes = false;
+            self.up_ch_modes.clear();
+            if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                for ch in 0..h.up_chs_size {
+                    // The firmware sets the modes again on every boot, so keep re-applying
+                    // the ones chosen here.
+                    if let Some(mode) = self.forced_ch_modes.get(&ch) {
+                        let _ = h.rtt_set_channel_mode(self.cur_target_core_idx, ch, *mode);
+                    }
+                    let mode = h
+                        .rtt_channel_mode(self.cur_target_core_idx, ch)
+                        .unwrap_or_default();
+                    self.up_ch_modes.push(mode);
+                }
+            }
+        }
+
+        /// Drains the RTT channel on its interval, also while another page is shown
+        /// so the target buffer doesn't overflow.
+        pub fn poll(&mut self, ctx: &eframe::egui::Context) {
+            if self.rtt_poll_interval_ms == 0 {
+                self.rtt_poll_interval_ms = m_config::RTT_POLL_INTERVAL_MS;
+            }
+            if self.rtt_read_buf_size == 0 {
+                self.rtt_read_buf_size = m_config::RTT_READ_BUF_SIZE;
+            }
+            self.triggers.reap_commands();
+
+            let poll_interval = Duration::from_millis(self.rtt_poll_interval_ms);
+            if self.b_try_to_read {
+                if self
+                    .last_rtt_poll
+                    .is_none_or(|t| t.elapsed() >= poll_interval)
+                {
+                    self.last_rtt_poll = Some(Instant::now());
+                    self.poll_rtt();
+                }
+                ctx.request_repaint_after(poll_interval);
+            }
+            self.triggers.alert_ui(ctx);
+        }
+
         pub fn ui(&mut self, ctx: &eframe::egui::Context, ui: &mut eframe::egui::Ui) {
             if self.probe_rs_handler.borrow_mut().is_none() {
                 self.probe_rs_handler = Some(ProbeRsHandler::default());
@@ -81,17 +500,18 @@ pub mod m_rtt_opts {
                         h.get_probes_list();
                     }
                 }
+                if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                    probe_config_ui(ui, h);
+                }
             });
             if let Some(h) = self.probe_rs_handler.borrow_mut() {
-                if h.chips_list.is_empty() {
-                    h.get_availabe_chips();
-                }
+                h.get_availabe_chips();
             }
 
             ui.horizontal(|ui| {
                 if let Some(h) = self.probe_rs_handler.borrow_mut() {
                     eframe::egui::ComboBox::from_label("target")
-                        .selected_text(self.target_chip_name.to_string())
+                        .selected_text(h.chip_label(&self.target_chip_name))
                         .show_ui(ui, |ui| {
                             for t in h.chips_list.iter() {
                                 if !self.filter_s.is_empty() {
@@ -99,14 +519,14 @@ pub mod m_rtt_opts {
                                         ui.selectable_value(
                                             &mut self.target_chip_name,
                                             t.to_string(),
-                                            t,
+                                            h.chip_label(t),
                                         );
                                     }
                                 } else {
                                     ui.selectable_value(
                                         &mut self.target_chip_name,
                                         t.to_string(),
-                                        t,
+                                        h.chip_label(t),
                                     );
                                 }
                             }
@@ -136,10 +556,27 @@ pub mod m_rtt_opts {
                         self.target_chip_name = "".to_owned();
                         self.b_get_scan_region = false;
                         self.selected_file = None;
+                        self.attach_strategy = None;
+                        self.b_rtt_lost = false;
+                        self.up_ch_modes.clear();
+                        self.forced_ch_modes.clear();
                         self.probe_rs_handler = None;
                     }
                 }
             });
+            if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                self.chip_info.ui(ui, &self.target_chip_name);
+                chip_detection_ui(ui, h, self.probe_selected_idx, &mut self.target_chip_name);
+                if unlock_ui(
+                    ui,
+                    h,
+                    &mut self.b_unlock_confirmed,
+                    self.probe_selected_idx,
+                    &self.target_chip_name,
+                ) {
+                    self.cur_target_core_idx = 0;
+                }
+            }
 
             ui.horizontal(|ui| {
                 if let Some(h) = self.probe_rs_handler.borrow_mut() {
@@ -156,14 +593,19 @@ pub mod m_rtt_opts {
                         });
 
                     if ui.button("attach rtt").clicked() {
-                        match h.attach_rtt(self.cur_target_core_idx) {
-                            Ok(_) => {}
-                            Err(_) => {}
+                        let strategy = RttAttachStrategy::Scan;
+                        if let Ok(true) =
+                            Self::run_attach_strategy(h, self.cur_target_core_idx, strategy)
+                        {
+                            self.attach_strategy = Some(strategy);
+                            self.attach_time = Some(Instant::now());
+                            self.b_rtt_lost = false;
+                            self.b_refresh_ch_modes = true;
                         }
-                        h.get_up_channels_size();
                     }
                 }
             });
+            self.gdb_server.ui(ui);
 
             ui.separator();
             ui.add_space(4.0);
@@ -196,15 +638,10 @@ pub mod m_rtt_opts {
 
                     if ui.button("get scan region from elf").clicked() {
                         if let Some(h) = self.probe_rs_handler.borrow_mut() {
-                            match h.get_scan_region(self.selected_file.borrow(), None) {
-                                Ok(_) => {
-                                    if let Some(sr) = h.scan_region.borrow() {
-                                        if let ScanRegion::Exact(_) = sr {
-                                            self.b_get_scan_region = true;
-                                        }
-                                    }
+                            if h.get_scan_region(self.selected_file.borrow(), None).is_ok() {
+                                if let Some(ScanRegion::Exact(_)) = h.scan_region.borrow() {
+                                    self.b_get_scan_region = true;
                                 }
-                                Err(_) => {}
                             }
                         }
                     }
@@ -213,11 +650,17 @@ pub mod m_rtt_opts {
                         ui.horizontal(|ui| {
                             if let Some(h) = self.probe_rs_handler.borrow_mut() {
                                 if ui.button("attach rtt region").clicked() {
-                                    match h.attach_rtt_region(self.cur_target_core_idx) {
-                                        Ok(_) => {}
-                                        Err(_) => {}
+                                    let strategy = RttAttachStrategy::Region;
+                                    if let Ok(true) = Self::run_attach_strategy(
+                                        h,
+                                        self.cur_target_core_idx,
+                                        strategy,
+                                    ) {
+                                        self.attach_strategy = Some(strategy);
+                                        self.attach_time = Some(Instant::now());
+                                        self.b_rtt_lost = false;
+                                        self.b_refresh_ch_modes = true;
                                     }
-                                    h.get_up_channels_size();
                                 }
                                 ui.add(
                                     eframe::egui::Slider::new(
@@ -229,14 +672,18 @@ pub mod m_rtt_opts {
                                 if ui.button("try to attach rtt rigion with timeout").clicked() {
                                     let time_out_duration =
                                         Duration::from_millis(self.retry_rtt_attach_time_out);
-                                    match h.attach_retry_loop(
+                                    let strategy =
+                                        RttAttachStrategy::RegionRetry(time_out_duration);
+                                    if let Ok(true) = Self::run_attach_strategy(
+                                        h,
                                         self.cur_target_core_idx,
-                                        time_out_duration,
+                                        strategy,
                                     ) {
-                                        Ok(_) => {}
-                                        Err(_) => {}
+                                        self.attach_strategy = Some(strategy);
+                                        self.attach_time = Some(Instant::now());
+                                        self.b_rtt_lost = false;
+                                        self.b_refresh_ch_modes = true;
                                     }
-                                    h.get_up_channels_size();
                                 }
                             }
                         });
@@ -248,7 +695,7 @@ pub mod m_rtt_opts {
 
             ui.horizontal(|ui| {
                 if let Some(h) = self.probe_rs_handler.borrow_mut() {
-                    if let Some(_rtt) = h.rtt.borrow() {
+                    if h.rtt.is_some() || self.b_rtt_lost {
                         eframe::egui::ComboBox::from_label("channel")
                             .selected_text(format!("{}", self.cur_target_channel_idx))
                             .show_ui(ui, |ui| {
@@ -263,40 +710,130 @@ pub mod m_rtt_opts {
 
                         ui.add_space(4.0);
                         ui.checkbox(&mut self.b_try_to_read, "try to read");
+                        ui.checkbox(&mut self.b_auto_reattach, "auto re-attach")
+                            .on_hover_text(
+                                "re-run the last rtt attach after a target reset or re-flash",
+                            );
+                        if self.b_rtt_lost {
+                            ui.label("target reset, waiting for rtt control block...");
+                        }
                     }
                 }
             });
+            ui.horizontal(|ui| {
+                ui.add(
+                    eframe::egui::Slider::new(&mut self.rtt_poll_interval_ms, 1..=1000)
+                        .text("poll interval (ms)"),
+                );
+                ui.add(
+                    eframe::egui::Slider::new(&mut self.rtt_read_buf_size, 64..=65536)
+                        .logarithmic(true)
+                        .text("read buffer (bytes)"),
+                );
+            });
+            ui.horizontal(|ui| {
+                let mode = self
+                    .ts_modes
+                    .entry(self.cur_target_channel_idx)
+                    .or_default();
+                eframe::egui::ComboBox::from_label("timestamp")
+                    .selected_text(
+                        TIMESTAMP_MODES
+                            .iter()
+                            .find(|(m, _)| m == mode)
+                            .map_or("", |(_, label)| label),
+                    )
+                    .show_ui(ui, |ui| {
+                        for (m, label) in TIMESTAMP_MODES {
+                            ui.selectable_value(mode, m, label);
+                        }
+                    });
+                ui.checkbox(&mut self.b_hide_seq_num, "hide sequence numbers");
+            });
 
-            if self.log_buf.len() >= self.n_display_row {
-                self.log_buf.pop_front();
+            if self.b_refresh_ch_modes {
+                self.refresh_channel_modes();
             }
-            let mut buf = [0u8; 128];
-            if self.b_try_to_read {
-                if let Some(h) = self.probe_rs_handler.borrow_mut() {
-                    match h.rtt_read_from_channel(
-                        &mut buf,
-                        self.cur_target_channel_idx,
-                        self.cur_target_channel_idx,
-                    ) {
-                        Ok(s) => {
-                            let read_size = s;
-                            if read_size > 0 {
-                                let local_date_time = Local::now();
-                                let ymdhms = local_date_time.format("%Y-%m-%d %H:%M:%S%.3f");
-                                let text = format!(
-                                    "{}: {} {}",
-                                    self.n_items,
-                                    ymdhms,
-                                    String::from_utf8_lossy(&buf)
-                                );
-                                self.log_buf.push_back(text);
-                                self.n_items = self.n_items.wrapping_add(1);
-                            }
-                        }
-                        Err(e) => {
-                            ui.label(format!("{:#?}", e));
-                        }
+            if !self.up_ch_modes.is_empty() {
+                eframe::egui::CollapsingHeader::new("up channels").show(ui, |ui| {
+                    if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                        eframe::egui::Grid::new("rtt_up_channels")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label("channel");
+                                ui.label("name");
+                                ui.label("size");
+                                ui.label("mode");
+                                ui.label("bytes/s");
+                                ui.label("total bytes");
+                                ui.label("est. overflows");
+                                ui.end_row();
+                                for (ch, mode) in self.up_ch_modes.iter_mut().enumerate() {
+                                    let (name, size) = match h.rtt.borrow_mut() {
+                                        Some(r) => match r.up_channel(ch) {
+                                            Some(up_ch) => (
+                                                up_ch.name().unwrap_or_default().to_owned(),
+                                                up_ch.buffer_size(),
+                                            ),
+                                            None => (String::new(), 0),
+                                        },
+                                        None => (String::new(), 0),
+                                    };
+                                    ui.label(format!("{}", ch));
+                                    ui.label(name);
+                                    ui.label(format!("{}", size));
+                                    let mut selected = *mode;
+                                    eframe::egui::ComboBox::from_id_salt(("rtt_ch_mode", ch))
+                                        .selected_text(channel_mode_label(selected))
+                                        .show_ui(ui, |ui| {
+                                            for (m, label) in CHANNEL_MODES {
+                                                ui.selectable_value(&mut selected, Some(m), label);
+                                            }
+                                        });
+                                    if selected != *mode {
+                                        if let Some(m) = selected {
+                                            match h.rtt_set_channel_mode(
+                                                self.cur_target_core_idx,
+                                                ch,
+                                                m,
+                                            ) {
+                                                Ok(_) => {
+                                                    self.forced_ch_modes.insert(ch, m);
+                                                    *mode = selected;
+                                                }
+                                                Err(e) => {
+                                                    ui.label(format!("{}", e));
+                                                }
+                                            }
+                                        }
+                                    }
+                                    match self.ch_stats.get(&ch) {
+                                        Some(stats) => {
+                                            ui.label(format!("{:.1}", stats.bytes_per_sec));
+                                            ui.label(format!("{}", stats.total_bytes));
+                                            ui.label(format!("{}", stats.overflows));
+                                        }
+                                        None => {
+                                            ui.label("-");
+                                            ui.label("-");
+                                            ui.label("-");
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
                     }
+                });
+            }
+
+            eframe::egui::CollapsingHeader::new("triggers").show(ui, |ui| {
+                self.triggers.ui(ui);
+            });
+
+            let poll_interval = Duration::from_millis(self.rtt_poll_interval_ms);
+            if !self.b_auto_reattach {
+                if let Some(e) = self.rtt_read_err.borrow() {
+                    ui.label(e);
                 }
             }
 
@@ -308,16 +845,22 @@ pub mod m_rtt_opts {
                 .stick_to_bottom(true)
                 .auto_shrink(false)
                 .show_rows(ui, row_height, self.n_items, |ui, row_range| {
-                    let row_start = row_range.start;
-                    self.n_display_row = row_range.len();
+                    // Older rows than the kept history scroll out as empty lines.
+                    let first_kept = self.n_items.saturating_sub(self.log_buf.len());
                     for row in row_range {
-                        if let Some(t) = self.log_buf.get(row - row_start) {
+                        if row < first_kept {
+                            ui.label("");
+                            continue;
+                        }
+                        if let Some(l) = self.log_buf.get(row - first_kept) {
+                            let mode = self.ts_modes.get(&l.channel).copied().unwrap_or_default();
+                            let t = format_rtt_line(l, mode, self.b_hide_seq_num);
                             let label = eframe::egui::Label::new(t).extend();
                             ui.add(label);
                         }
                     }
                 });
-            ui.ctx().request_repaint();
+            ui.ctx().request_repaint_after(poll_interval);
         }
     }
 }
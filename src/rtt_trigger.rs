@@ -0,0 +1,275 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_rtt_trigger {
+    use crate::configuration::m_config;
+    use chrono::{DateTime, Local};
+    use regex::Regex;
+    use std::{
+        collections::VecDeque,
+        process::{Child, Command},
+        time::{Duration, Instant},
+    };
+
+    #[derive(Default)]
+    pub struct TriggerRule {
+        pattern: String,
+        compiled_pattern: String,
+        regex: Option<Regex>,
+        regex_err: Option<String>,
+        enabled: bool,
+        pub b_alert: bool,
+        pub b_stop_polling: bool,
+        pub b_halt_core: bool,
+        pub b_snapshot: bool,
+        pub b_run_command: bool,
+        pub command: String,
+        hits: usize,
+        last_fired: Option<Instant>,
+    }
+
+    impl TriggerRule {
+        fn new(pattern: &str) -> Self {
+            let mut rule = Self {
+                pattern: pattern.to_owned(),
+                enabled: true,
+                b_alert: true,
+                ..Default::default()
+            };
+            rule.compile();
+            rule
+        }
+
+        fn compile(&mut self) {
+            self.compiled_pattern = self.pattern.clone();
+            match Regex::new(&self.pattern) {
+                Ok(r) => {
+                    self.regex = Some(r);
+                    self.regex_err = None;
+                }
+                Err(e) => {
+                    self.regex = None;
+                    self.regex_err = Some(format!("{}", e));
+                }
+            }
+        }
+    }
+
+    pub struct TriggerEvent {
+        pub time: DateTime<Local>,
+        pub pattern: String,
+        pub line: String,
+        pub notes: Vec<String>,
+    }
+
+    #[derive(Default)]
+    pub struct RttTriggers {
+        rules: Vec<TriggerRule>,
+        new_pattern: String,
+        pub snapshot_dir: String,
+        history: VecDeque<TriggerEvent>,
+        alerts: Vec<String>,
+        /// Commands still running, waited on so they don't linger as zombies.
+        children: Vec<Child>,
+    }
+
+    impl RttTriggers {
+        /// Returns the rules that fire on `line`. Rules that already fired within the
+        /// cooldown only count the hit, so a flood of matching lines does not re-run
+        /// snapshots or commands for every line.
+        pub fn check(&mut self, line: &str) -> Vec<usize> {
+            let cooldown = Duration::from_millis(m_config::RTT_TRIGGER_COOLDOWN_MS);
+            let mut fired = Vec::new();
+            for (i, rule) in self.rules.iter_mut().enumerate() {
+                if !rule.enabled {
+                    continue;
+                }
+                if let Some(r) = rule.regex.as_ref() {
+                    if r.is_match(line) {
+                        rule.hits = rule.hits.wrapping_add(1);
+                        if rule.last_fired.is_none_or(|t| t.elapsed() >= cooldown) {
+                            rule.last_fired = Some(Instant::now());
+                            fired.push(i);
+                        }
+                    }
+                }
+            }
+            fired
+        }
+
+        pub fn rule(&self, idx: usize) -> Option<&TriggerRule> {
+            self.rules.get(idx)
+        }
+
+        /// Forgets the commands that exited.
+        pub fn reap_commands(&mut self) {
+            self.children
+                .retain_mut(|c| matches!(c.try_wait(), Ok(None)));
+        }
+
+        pub fn run_command(&mut self, idx: usize, line: &str) -> Result<(), std::io::Error> {
+            self.reap_commands();
+            if let Some(rule) = self.rules.get(idx) {
+                let mut cmd = if cfg!(target_os = "windows") {
+                    let mut c = Command::new("cmd");
+                    c.arg("/C");
+                    c
+                } else {
+                    let mut c = Command::new("sh");
+                    c.arg("-c");
+                    c
+                };
+                let child = cmd
+                    .arg(&rule.command)
+                    .env(m_config::RTT_TRIGGER_LINE_ENV, line)
+                    .spawn()?;
+                self.children.push(child);
+            }
+            Ok(())
+        }
+
+        pub fn record(&mut self, idx: usize, line: &str, notes: Vec<String>) {
+            let Some(rule) = self.rules.get(idx) else {
+                return;
+            };
+            if rule.b_alert {
+                self.alerts
+                    .push(format!("\"{}\" matched: {}", rule.pattern, line));
+            }
+            self.history.push_back(TriggerEvent {
+                time: Local::now(),
+                pattern: rule.pattern.clone(),
+                line: line.to_owned(),
+                notes,
+            });
+            while self.history.len() > m_config::RTT_TRIGGER_HISTORY_LEN {
+                self.history.pop_front();
+            }
+        }
+
+        pub fn ui(&mut self, ui: &mut eframe::egui::Ui) {
+            ui.horizontal(|ui| {
+                ui.add(
+                    eframe::egui::TextEdit::singleline(&mut self.new_pattern)
+                        .hint_text("regex, e.g. PANIC|assert failed")
+                        .desired_width(240.0),
+                );
+                if ui.button("add trigger").clicked() && !self.new_pattern.is_empty() {
+                    self.rules.push(TriggerRule::new(&self.new_pattern));
+                    self.new_pattern.clear();
+                }
+                ui.add(
+                    eframe::egui::TextEdit::singleline(&mut self.snapshot_dir)
+                        .hint_text("snapshot dir (default: current)")
+                        .desired_width(200.0),
+                );
+            });
+
+            let mut remove_idx = None;
+            for (i, rule) in self.rules.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut rule.enabled, "");
+                    ui.add(
+                        eframe::egui::TextEdit::singleline(&mut rule.pattern).desired_width(160.0),
+                    );
+                    if rule.pattern != rule.compiled_pattern {
+                        rule.compile();
+                    }
+                    ui.checkbox(&mut rule.b_alert, "alert");
+                    ui.checkbox(&mut rule.b_stop_polling, "stop polling");
+                    ui.checkbox(&mut rule.b_halt_core, "halt core");
+                    ui.checkbox(&mut rule.b_snapshot, "snapshot log");
+                    ui.checkbox(&mut rule.b_run_command, "run");
+                    ui.add_enabled(
+                        rule.b_run_command,
+                        eframe::egui::TextEdit::singleline(&mut rule.command)
+                            .hint_text("shell command")
+                            .desired_width(160.0),
+                    );
+                    ui.label(format!("hits: {}", rule.hits));
+                    if ui.button("remove").clicked() {
+                        remove_idx = Some(i);
+                    }
+                });
+                if let Some(e) = rule.regex_err.as_ref() {
+                    ui.colored_label(ui.visuals().error_fg_color, e);
+                }
+            }
+            if let Some(i) = remove_idx {
+                self.rules.remove(i);
+            }
+
+            eframe::egui::CollapsingHeader::new(format!(
+                "trigger history ({})",
+                self.history.len()
+            ))
+            .show(ui, |ui| {
+                if ui.button("clear").clicked() {
+                    self.history.clear();
+                }
+                eframe::egui::ScrollArea::vertical()
+                    .max_height(120.0)
+                    .show(ui, |ui| {
+                        for ev in self.history.iter().rev() {
+                            ui.label(format!(
+                                "{} \"{}\": {}",
+                                ev.time.format("%Y-%m-%d %H:%M:%S%.3f"),
+                                ev.pattern,
+                                ev.line
+                            ));
+                            for n in ev.notes.iter() {
+                                ui.label(format!("    {}", n));
+                            }
+                        }
+                    });
+            });
+        }
+
+        pub fn alert_ui(&mut self, ctx: &eframe::egui::Context) {
+            if self.alerts.is_empty() {
+                return;
+            }
+            let mut b_dismiss = false;
+            eframe::egui::Window::new("RTT trigger")
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    for a in self
+                        .alerts
+                        .iter()
+                        .rev()
+                        .take(m_config::RTT_TRIGGER_ALERTS_SHOWN)
+                    {
+                        ui.colored_label(ui.visuals().warn_fg_color, a);
+                    }
+                    if self.alerts.len() > m_config::RTT_TRIGGER_ALERTS_SHOWN {
+                        ui.label(format!(
+                            "... and {} more",
+                            self.alerts.len() - m_config::RTT_TRIGGER_ALERTS_SHOWN
+                        ));
+                    }
+                    if ui.button("dismiss").clicked() {
+                        b_dismiss = true;
+                    }
+                });
+            if b_dismiss {
+                self.alerts.clear();
+            }
+        }
+    }
+}
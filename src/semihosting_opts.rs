@@ -0,0 +1,154 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_semihosting_opts {
+    use crate::configuration::m_config;
+    use crate::probe_rs_invoke::probe_rs_integration::{ProbeRsHandler, SemihostingEvent};
+    use probe_rs::{BreakpointCause, CoreStatus, HaltReason};
+
+    pub struct SemihostingView {
+        b_enabled: bool,
+        b_auto_scroll: bool,
+        lines: Vec<String>,
+        /// Output of the current line, not terminated by a newline yet.
+        pending: String,
+        /// Exit code per core, None until the target exits.
+        exits: Vec<Option<Option<u32>>>,
+        rst_info: Option<String>,
+    }
+
+    impl Default for SemihostingView {
+        fn default() -> Self {
+            Self {
+                b_enabled: true,
+                b_auto_scroll: true,
+                lines: Vec::new(),
+                pending: String::new(),
+                exits: Vec::new(),
+                rst_info: None,
+            }
+        }
+    }
+
+    impl SemihostingView {
+        fn push_output(&mut self, text: &str) {
+            for c in text.chars() {
+                if c == '\n' {
+                    self.lines.push(std::mem::take(&mut self.pending));
+                } else if c != '\r' {
+                    self.pending.push(c);
+                }
+            }
+            let n = self.lines.len();
+            if n > m_config::SEMIHOSTING_LOG_HISTORY_LEN {
+                self.lines
+                    .drain(..n - m_config::SEMIHOSTING_LOG_HISTORY_LEN);
+            }
+        }
+
+        /// Services the cores halted on a semihosting request. Returns true when a
+        /// request was handled, so the caller can check the status again soon.
+        pub fn poll(
+            &mut self,
+            h: &mut ProbeRsHandler,
+            cores_status: &[Option<CoreStatus>],
+        ) -> bool {
+            if !self.b_enabled {
+                return false;
+            }
+            self.exits.resize(cores_status.len(), None);
+            let mut b_serviced = false;
+            for (c, status) in cores_status.iter().enumerate() {
+                // A new run after reset can exit again.
+                if matches!(status, Some(CoreStatus::Running)) {
+                    self.exits[c] = None;
+                }
+                if !matches!(
+                    status,
+                    Some(CoreStatus::Halted(HaltReason::Breakpoint(
+                        BreakpointCause::Semihosting(_)
+                    )))
+                ) || self.exits[c].is_some()
+                {
+                    continue;
+                }
+                match h.service_semihosting(c) {
+                    Ok(Some(SemihostingEvent::Output(text))) => {
+                        self.push_output(&text);
+                        b_serviced = true;
+                    }
+                    Ok(Some(SemihostingEvent::Exit(code))) => {
+                        if !self.pending.is_empty() {
+                            self.push_output("\n");
+                        }
+                        let code_s = code.map_or("unknown".to_owned(), |v| format!("{}", v));
+                        self.push_output(&format!("--- core {} exited, code {} ---\n", c, code_s));
+                        self.exits[c] = Some(code);
+                    }
+                    Ok(Some(SemihostingEvent::Unsupported(cmd))) => {
+                        self.rst_info = Some(format!("unsupported semihosting request: {}", cmd));
+                        b_serviced = true;
+                    }
+                    Ok(None) => {}
+                    Err(e) => self.rst_info = Some(format!("{}", e)),
+                }
+            }
+            b_serviced
+        }
+
+        pub fn ui(&mut self, ui: &mut eframe::egui::Ui, core_idx: usize) {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.b_enabled, "service requests");
+                ui.checkbox(&mut self.b_auto_scroll, "auto scroll");
+                if ui.button("clear").clicked() {
+                    self.lines.clear();
+                    self.pending.clear();
+                    self.exits.iter_mut().for_each(|e| *e = None);
+                    self.rst_info.take();
+                }
+                ui.separator();
+                match self.exits.get(core_idx).copied().flatten() {
+                    Some(Some(0)) => ui.label("exited successfully"),
+                    Some(Some(code)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, format!("exit code {}", code))
+                    }
+                    Some(None) => ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        "exited with an error, no exit code",
+                    ),
+                    None => ui.label("-"),
+                };
+            });
+            if let Some(info) = self.rst_info.as_ref() {
+                ui.label(info);
+            }
+
+            eframe::egui::ScrollArea::vertical()
+                .auto_shrink(false)
+                .stick_to_bottom(self.b_auto_scroll)
+                .show(ui, |ui| {
+                    for l in self.lines.iter() {
+                        ui.monospace(l);
+                    }
+                    if !self.pending.is_empty() {
+                        ui.monospace(&self.pending);
+                    }
+                });
+        }
+    }
+}
@@ -0,0 +1,414 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+/// CMSIS-SVD device descriptions, flattened to peripherals, registers and fields
+/// with clusters and dim arrays expanded.
+pub mod m_svd {
+    use crate::debug_opts::m_debug_opts::parse_number;
+    use quick_xml::events::{BytesStart, Event};
+    use quick_xml::Reader;
+    use std::{error::Error, fs, path::Path};
+
+    /// A bare XML element, SVD files are parsed into these first.
+    #[derive(Default)]
+    struct Node {
+        name: String,
+        attributes: Vec<(String, String)>,
+        text: String,
+        children: Vec<Node>,
+    }
+
+    impl Node {
+        fn child(&self, name: &str) -> Option<&Node> {
+            self.children.iter().find(|c| c.name == name)
+        }
+
+        fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Node> {
+            self.children.iter().filter(move |c| c.name == name)
+        }
+
+        fn attribute(&self, name: &str) -> Option<&str> {
+            self.attributes
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.as_str())
+        }
+
+        fn text_of(&self, name: &str) -> Option<String> {
+            self.child(name).map(|c| c.text.trim().to_owned())
+        }
+
+        fn number_of(&self, name: &str) -> Option<u64> {
+            self.child(name).and_then(|c| parse_svd_number(&c.text))
+        }
+    }
+
+    fn start_node(e: &BytesStart) -> Result<Node, Box<dyn Error>> {
+        let mut attributes = Vec::new();
+        for a in e.attributes() {
+            let a = a?;
+            attributes.push((
+                String::from_utf8_lossy(a.key.local_name().as_ref()).into_owned(),
+                a.unescape_value()?.into_owned(),
+            ));
+        }
+        Ok(Node {
+            name: String::from_utf8_lossy(e.local_name().as_ref()).into_owned(),
+            attributes,
+            ..Default::default()
+        })
+    }
+
+    fn parse_xml(text: &str) -> Result<Node, Box<dyn Error>> {
+        let mut reader = Reader::from_str(text);
+        reader.trim_text(true);
+        let mut stack = vec![Node::default()];
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) => stack.push(start_node(&e)?),
+                Event::Empty(e) => {
+                    let node = start_node(&e)?;
+                    if let Some(parent) = stack.last_mut() {
+                        parent.children.push(node);
+                    }
+                }
+                Event::Text(e) => {
+                    if let Some(node) = stack.last_mut() {
+                        node.text += &e.unescape()?;
+                    }
+                }
+                Event::CData(e) => {
+                    if let Some(node) = stack.last_mut() {
+                        node.text += &String::from_utf8_lossy(&e);
+                    }
+                }
+                Event::End(_) if stack.len() > 1 => {
+                    let node = stack.pop().unwrap_or_default();
+                    if let Some(parent) = stack.last_mut() {
+                        parent.children.push(node);
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        Ok(stack.swap_remove(0))
+    }
+
+    /// SVD numbers are decimal, `0x` hex, or `#`/`0b` binary.
+    fn parse_svd_number(text: &str) -> Option<u64> {
+        let t = text.trim();
+        if let Some(bin) = t.strip_prefix('#') {
+            return u64::from_str_radix(bin, 2).ok();
+        }
+        parse_number(t).and_then(|n| u64::try_from(n).ok())
+    }
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    pub enum Access {
+        #[default]
+        ReadWrite,
+        ReadOnly,
+        WriteOnly,
+    }
+
+    impl Access {
+        fn parse(text: Option<String>) -> Option<Self> {
+            match text?.as_str() {
+                "read-only" => Some(Access::ReadOnly),
+                "write-only" | "writeOnce" => Some(Access::WriteOnly),
+                _ => Some(Access::ReadWrite),
+            }
+        }
+
+        pub fn is_readable(&self) -> bool {
+            *self != Access::WriteOnly
+        }
+
+        pub fn is_writable(&self) -> bool {
+            *self != Access::ReadOnly
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct EnumValue {
+        pub name: String,
+        pub description: String,
+        pub value: u64,
+    }
+
+    #[derive(Clone)]
+    pub struct Field {
+        pub name: String,
+        pub description: String,
+        pub bit_offset: u32,
+        pub bit_width: u32,
+        pub access: Access,
+        pub values: Vec<EnumValue>,
+    }
+
+    impl Field {
+        pub fn mask(&self) -> u64 {
+            let bits = if self.bit_width >= 64 {
+                u64::MAX
+            } else {
+                (1u64 << self.bit_width) - 1
+            };
+            bits << self.bit_offset
+        }
+
+        pub fn extract(&self, register: u64) -> u64 {
+            (register & self.mask()) >> self.bit_offset
+        }
+
+        pub fn insert(&self, register: u64, value: u64) -> u64 {
+            (register & !self.mask()) | ((value << self.bit_offset) & self.mask())
+        }
+
+        pub fn value_name(&self, value: u64) -> Option<&str> {
+            self.values
+                .iter()
+                .find(|v| v.value == value)
+                .map(|v| v.name.as_str())
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct Register {
+        pub name: String,
+        pub description: String,
+        pub address: u64,
+        pub size: u32,
+        pub access: Access,
+        pub reset_value: u64,
+        pub fields: Vec<Field>,
+    }
+
+    pub struct Peripheral {
+        pub name: String,
+        pub description: String,
+        pub base_address: u64,
+        pub registers: Vec<Register>,
+    }
+
+    #[derive(Default)]
+    pub struct SvdDevice {
+        pub name: String,
+        pub peripherals: Vec<Peripheral>,
+    }
+
+    /// Register properties inherited from the device down to the registers.
+    #[derive(Clone, Copy)]
+    struct Defaults {
+        size: u32,
+        access: Access,
+        reset_value: u64,
+    }
+
+    impl Defaults {
+        fn inherit(&self, node: &Node) -> Self {
+            Self {
+                size: node.number_of("size").map_or(self.size, |s| s as u32),
+                access: Access::parse(node.text_of("access")).unwrap_or(self.access),
+                reset_value: node.number_of("resetValue").unwrap_or(self.reset_value),
+            }
+        }
+    }
+
+    /// Expands a `dim` array into (name, offset) pairs, or the element itself.
+    fn expand_dim(node: &Node, name: &str) -> Vec<(String, u64)> {
+        let Some(dim) = node.number_of("dim") else {
+            return vec![(name.to_owned(), 0)];
+        };
+        let increment = node.number_of("dimIncrement").unwrap_or(0);
+        let indices: Vec<String> = match node.text_of("dimIndex") {
+            Some(list) if list.contains(',') => {
+                list.split(',').map(|s| s.trim().to_owned()).collect()
+            }
+            Some(range) if range.contains('-') => {
+                let (a, b) = range.split_once('-').unwrap_or_default();
+                match (a.trim().parse::<u64>(), b.trim().parse::<u64>()) {
+                    (Ok(a), Ok(b)) => (a..=b).map(|i| i.to_string()).collect(),
+                    _ => (0..dim).map(|i| i.to_string()).collect(),
+                }
+            }
+            _ => (0..dim).map(|i| i.to_string()).collect(),
+        };
+        indices
+            .iter()
+            .take(dim as usize)
+            .enumerate()
+            .map(|(i, idx)| {
+                let n = if name.contains("[%s]") {
+                    name.replace("[%s]", &format!("[{}]", idx))
+                } else {
+                    name.replace("%s", idx)
+                };
+                (n, i as u64 * increment)
+            })
+            .collect()
+    }
+
+    fn parse_field(node: &Node, access: Access) -> Option<Field> {
+        let name = node.text_of("name")?;
+        let (bit_offset, bit_width) =
+            if let (Some(o), Some(w)) = (node.number_of("bitOffset"), node.number_of("bitWidth")) {
+                (o as u32, w as u32)
+            } else if let (Some(l), Some(m)) = (node.number_of("lsb"), node.number_of("msb")) {
+                (l as u32, (m.checked_sub(l)? + 1) as u32)
+            } else {
+                let range = node.text_of("bitRange")?;
+                let (m, l) = range.trim_matches(['[', ']']).split_once(':')?;
+                let (m, l) = (m.trim().parse::<u32>().ok()?, l.trim().parse::<u32>().ok()?);
+                (l, m.checked_sub(l)? + 1)
+            };
+        let mut values = Vec::new();
+        for list in node.children("enumeratedValues") {
+            for v in list.children("enumeratedValue") {
+                // Values with "don't care" bits and isDefault entries have no single value.
+                let (Some(name), Some(value)) = (v.text_of("name"), v.number_of("value")) else {
+                    continue;
+                };
+                values.push(EnumValue {
+                    name,
+                    description: v.text_of("description").unwrap_or_default(),
+                    value,
+                });
+            }
+        }
+        Some(Field {
+            name,
+            description: node.text_of("description").unwrap_or_default(),
+            bit_offset,
+            bit_width,
+            access: Access::parse(node.text_of("access")).unwrap_or(access),
+            values,
+        })
+    }
+
+    fn parse_registers(
+        node: &Node,
+        base: u64,
+        prefix: &str,
+        defaults: Defaults,
+        registers: &mut Vec<Register>,
+    ) {
+        for child in node.children.iter() {
+            let b_cluster = child.name == "cluster";
+            if !b_cluster && child.name != "register" {
+                continue;
+            }
+            let Some(name) = child.text_of("name") else {
+                continue;
+            };
+            let offset = child.number_of("addressOffset").unwrap_or(0);
+            let d = defaults.inherit(child);
+            for (n, dim_offset) in expand_dim(child, &name) {
+                let address = base + offset + dim_offset;
+                let full_name = format!("{}{}", prefix, n);
+                if b_cluster {
+                    parse_registers(child, address, &format!("{}.", full_name), d, registers);
+                    continue;
+                }
+                let mut fields: Vec<Field> = child
+                    .child("fields")
+                    .map(|f| {
+                        f.children("field")
+                            .filter_map(|f| parse_field(f, d.access))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                fields.sort_by_key(|f| std::cmp::Reverse(f.bit_offset));
+                registers.push(Register {
+                    name: full_name,
+                    description: child.text_of("description").unwrap_or_default(),
+                    address,
+                    size: d.size,
+                    access: d.access,
+                    reset_value: d.reset_value,
+                    fields,
+                });
+            }
+        }
+    }
+
+    impl SvdDevice {
+        pub fn load(svd_file: &Path) -> Result<Self, Box<dyn Error>> {
+            let text = fs::read_to_string(svd_file)?;
+            let root = parse_xml(&text)?;
+            let device = root.child("device").ok_or("not an SVD file, no <device>")?;
+            let defaults = Defaults {
+                size: 32,
+                access: Access::ReadWrite,
+                reset_value: 0,
+            }
+            .inherit(device);
+
+            let nodes: Vec<&Node> = device
+                .child("peripherals")
+                .map(|p| p.children("peripheral").collect())
+                .unwrap_or_default();
+            let mut peripherals: Vec<Peripheral> = Vec::new();
+            for p in nodes.iter() {
+                let Some(name) = p.text_of("name") else {
+                    continue;
+                };
+                let base_address = p.number_of("baseAddress").unwrap_or(0);
+                let d = defaults.inherit(p);
+                let mut registers = Vec::new();
+                if let Some(regs) = p.child("registers") {
+                    parse_registers(regs, base_address, "", d, &mut registers);
+                }
+                let mut description = p.text_of("description").unwrap_or_default();
+                // A derived peripheral reuses the registers of its base at its own address.
+                if let Some(base) = p
+                    .attribute("derivedFrom")
+                    .and_then(|b| peripherals.iter().find(|x| x.name == b))
+                {
+                    if registers.is_empty() {
+                        registers = base
+                            .registers
+                            .iter()
+                            .map(|r| Register {
+                                address: r.address - base.base_address + base_address,
+                                ..r.clone()
+                            })
+                            .collect();
+                    }
+                    if description.is_empty() {
+                        description = base.description.clone();
+                    }
+                }
+                peripherals.push(Peripheral {
+                    name,
+                    description,
+                    base_address,
+                    registers,
+                });
+            }
+            Ok(Self {
+                name: device.text_of("name").unwrap_or_default(),
+                peripherals,
+            })
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.peripherals.is_empty()
+        }
+    }
+}
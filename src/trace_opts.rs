@@ -0,0 +1,475 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_trace_opts {
+    use crate::chip_info_opts::m_chip_info_opts::ChipInfoView;
+    use crate::configuration::m_config;
+    use crate::elf_symbols::m_elf_symbols::ElfSymbols;
+    use crate::fault_opts::m_fault_opts::exception_name;
+    use crate::itm::m_itm::{ExceptionAction, ItmDecoder, ItmPacket};
+    use crate::probe_opts::m_probe_opts::{chip_detection_ui, probe_config_ui, unlock_ui};
+    use crate::probe_rs_invoke::probe_rs_integration::ProbeRsHandler;
+    use egui_file::FileDialog;
+    use std::{
+        borrow::{Borrow, BorrowMut},
+        collections::{BTreeMap, HashMap, VecDeque},
+        path::PathBuf,
+        time::{Duration, Instant},
+    };
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum TracePanel {
+        Port(u8),
+        Profile,
+    }
+
+    #[derive(Default)]
+    struct PortStream {
+        lines: VecDeque<String>,
+        /// Bytes of the current line, not terminated by a newline yet.
+        pending: Vec<u8>,
+    }
+
+    impl PortStream {
+        fn push(&mut self, payload: &[u8]) {
+            for b in payload {
+                match b {
+                    b'\n' => {
+                        let line = String::from_utf8_lossy(&self.pending).into_owned();
+                        self.lines.push_back(line);
+                        self.pending.clear();
+                    }
+                    b'\r' => {}
+                    b => self.pending.push(*b),
+                }
+            }
+            while self.lines.len() > m_config::SWO_LOG_HISTORY_LEN {
+                self.lines.pop_front();
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct ExceptionStats {
+        entered: u64,
+        exited: u64,
+        returned: u64,
+    }
+
+    #[derive(Default)]
+    pub struct SwoTrace {
+        probe_selected_idx: usize,
+        probe_rs_handler: Option<ProbeRsHandler>,
+        target_chip_name: String,
+        filter_s: String,
+        cur_target_core_idx: usize,
+        trace_clock_hz: u32,
+        swo_baud: u32,
+        b_pc_sampling: bool,
+        b_capturing: bool,
+        last_swo_poll: Option<Instant>,
+        decoder: ItmDecoder,
+        n_bytes: u64,
+        n_overflows: u64,
+        ports: BTreeMap<u8, PortStream>,
+        panel: Option<TracePanel>,
+        pc_samples: HashMap<u32, u64>,
+        n_sleep_samples: u64,
+        exceptions: BTreeMap<u16, ExceptionStats>,
+        file_dialog: Option<FileDialog>,
+        selected_file: Option<PathBuf>,
+        elf_symbols: ElfSymbols,
+        rst_info: Option<String>,
+        b_unlock_confirmed: bool,
+        chip_info: ChipInfoView,
+    }
+
+    impl SwoTrace {
+        fn clear(&mut self) {
+            self.decoder = ItmDecoder::default();
+            self.n_bytes = 0;
+            self.n_overflows = 0;
+            self.ports.clear();
+            self.pc_samples.clear();
+            self.n_sleep_samples = 0;
+            self.exceptions.clear();
+        }
+
+        fn handle_packet(&mut self, packet: ItmPacket) {
+            match packet {
+                ItmPacket::Instrumentation { port, payload } => {
+                    self.ports.entry(port).or_default().push(&payload);
+                    self.panel.get_or_insert(TracePanel::Port(port));
+                }
+                ItmPacket::PcSample(Some(pc)) => {
+                    *self.pc_samples.entry(pc).or_default() += 1;
+                }
+                ItmPacket::PcSample(None) => self.n_sleep_samples += 1,
+                ItmPacket::Exception { number, action } => {
+                    let stats = self.exceptions.entry(number).or_default();
+                    match action {
+                        ExceptionAction::Entered => stats.entered += 1,
+                        ExceptionAction::Exited => stats.exited += 1,
+                        ExceptionAction::Returned => stats.returned += 1,
+                    }
+                }
+                ItmPacket::Overflow => self.n_overflows += 1,
+            }
+        }
+
+        fn poll_swo(&mut self) {
+            if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                match h.read_swo() {
+                    Ok(data) => {
+                        self.n_bytes += data.len() as u64;
+                        for p in self.decoder.feed(&data) {
+                            self.handle_packet(p);
+                        }
+                    }
+                    Err(e) => {
+                        self.rst_info = Some(format!("{}", e));
+                        self.b_capturing = false;
+                    }
+                }
+            }
+        }
+
+        /// PC samples summed up per function, most sampled first.
+        fn profile(&self) -> Vec<(String, u64)> {
+            let mut by_func: HashMap<String, u64> = HashMap::new();
+            for (pc, n) in self.pc_samples.iter() {
+                let name = match self.elf_symbols.symbol_at(*pc as u64) {
+                    Some((s, _)) => s.name.clone(),
+                    None => format!("{:#010x}", pc),
+                };
+                *by_func.entry(name).or_default() += n;
+            }
+            let mut rows: Vec<(String, u64)> = by_func.into_iter().collect();
+            rows.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            rows
+        }
+
+        fn profile_ui(&self, ui: &mut eframe::egui::Ui) {
+            let total: u64 = self.pc_samples.values().sum::<u64>() + self.n_sleep_samples;
+            ui.label(format!(
+                "{} pc samples, {} while sleeping",
+                total, self.n_sleep_samples
+            ));
+            eframe::egui::ScrollArea::vertical()
+                .auto_shrink(false)
+                .show(ui, |ui| {
+                    eframe::egui::Grid::new("trace_profile")
+                        .striped(true)
+                        .num_columns(3)
+                        .show(ui, |ui| {
+                            ui.strong("function");
+                            ui.strong("samples");
+                            ui.strong("%");
+                            ui.end_row();
+                            for (name, n) in self
+                                .profile()
+                                .into_iter()
+                                .take(m_config::SWO_PROFILE_ROWS_SHOWN)
+                            {
+                                ui.monospace(name);
+                                ui.label(format!("{}", n));
+                                ui.label(format!("{:.1}", n as f64 * 100.0 / total as f64));
+                                ui.end_row();
+                            }
+                        });
+                    ui.separator();
+                    eframe::egui::Grid::new("trace_exceptions")
+                        .striped(true)
+                        .num_columns(4)
+                        .show(ui, |ui| {
+                            ui.strong("exception");
+                            ui.strong("entered");
+                            ui.strong("exited");
+                            ui.strong("returned");
+                            ui.end_row();
+                            for (number, stats) in self.exceptions.iter() {
+                                ui.label(exception_name(*number as u64));
+                                ui.label(format!("{}", stats.entered));
+                                ui.label(format!("{}", stats.exited));
+                                ui.label(format!("{}", stats.returned));
+                                ui.end_row();
+                            }
+                        });
+                });
+        }
+
+        /// Drains the SWO stream on its interval, also while another page is shown
+        /// so the probe buffer doesn't overflow.
+        pub fn poll(&mut self, ctx: &eframe::egui::Context) {
+            if self.b_capturing {
+                let interval = Duration::from_millis(m_config::SWO_POLL_INTERVAL_MS);
+                if self.last_swo_poll.is_none_or(|t| t.elapsed() >= interval) {
+                    self.last_swo_poll = Some(Instant::now());
+                    self.poll_swo();
+                }
+                ctx.request_repaint_after(interval);
+            }
+        }
+
+        pub fn ui(&mut self, ctx: &eframe::egui::Context, ui: &mut eframe::egui::Ui) {
+            if self.probe_rs_handler.borrow_mut().is_none() {
+                self.probe_rs_handler = Some(ProbeRsHandler::default());
+            }
+            if self.trace_clock_hz == 0 {
+                self.trace_clock_hz = m_config::SWO_DEFAULT_TRACE_CLOCK_HZ;
+            }
+            if self.swo_baud == 0 {
+                self.swo_baud = m_config::SWO_DEFAULT_BAUD;
+            }
+            if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                if h.probes_list.is_empty() {
+                    h.get_probes_list();
+                }
+            }
+
+            ui.horizontal(|ui| {
+                eframe::egui::ComboBox::from_label("probe")
+                    .selected_text(format!("{}", self.probe_selected_idx))
+                    .show_ui(ui, |ui| {
+                        if let Some(h) = self.probe_rs_handler.borrow() {
+                            for (i, p) in h.probes_list.iter().enumerate() {
+                                ui.selectable_value(
+                                    &mut self.probe_selected_idx,
+                                    i,
+                                    format!(
+                                        "{} (pid: {} vid: {})",
+                                        p.identifier.as_str(),
+                                        p.product_id,
+                                        p.vendor_id
+                                    ),
+                                );
+                            }
+                        }
+                    });
+                if ui.button("refresh").clicked() {
+                    if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                        h.get_probes_list();
+                    }
+                }
+                if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                    probe_config_ui(ui, h);
+                }
+            });
+            if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                h.get_availabe_chips();
+            }
+
+            ui.horizontal(|ui| {
+                if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                    eframe::egui::ComboBox::from_label("target")
+                        .selected_text(h.chip_label(&self.target_chip_name))
+                        .show_ui(ui, |ui| {
+                            for t in h.chips_list.iter() {
+                                if !self.filter_s.is_empty() {
+                                    if t.contains(&self.filter_s) {
+                                        ui.selectable_value(
+                                            &mut self.target_chip_name,
+                                            t.to_string(),
+                                            h.chip_label(t),
+                                        );
+                                    }
+                                } else {
+                                    ui.selectable_value(
+                                        &mut self.target_chip_name,
+                                        t.to_string(),
+                                        h.chip_label(t),
+                                    );
+                                }
+                            }
+                        });
+
+                    ui.add(
+                        eframe::egui::TextEdit::singleline(&mut self.filter_s)
+                            .hint_text("chips filter")
+                            .desired_width(100.0),
+                    );
+
+                    if ui.button("attach").clicked() {
+                        match h.attach_target(self.probe_selected_idx, &self.target_chip_name) {
+                            Ok(_) => {
+                                self.rst_info.take();
+                            }
+                            Err(e) => {
+                                self.rst_info = Some(format!("{:#?}", e));
+                            }
+                        }
+                        h.get_core_num();
+                        self.b_capturing = false;
+                    }
+                    if ui.button("attach under reset").clicked() {
+                        match h.attach_target_under_reset(
+                            self.probe_selected_idx,
+                            &self.target_chip_name,
+                        ) {
+                            Ok(_) => {
+                                self.rst_info.take();
+                            }
+                            Err(e) => {
+                                self.rst_info = Some(format!("{:#?}", e));
+                            }
+                        }
+                        h.get_core_num();
+                        self.b_capturing = false;
+                    }
+                    if ui.button("reset all").clicked() && h.reset_all_cores().is_ok() {
+                        self.target_chip_name = "".to_owned();
+                        self.cur_target_core_idx = 0;
+                        self.b_capturing = false;
+                        self.rst_info.take();
+                        self.probe_rs_handler = None;
+                    }
+                }
+            });
+            if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                self.chip_info.ui(ui, &self.target_chip_name);
+                chip_detection_ui(ui, h, self.probe_selected_idx, &mut self.target_chip_name);
+                if unlock_ui(
+                    ui,
+                    h,
+                    &mut self.b_unlock_confirmed,
+                    self.probe_selected_idx,
+                    &self.target_chip_name,
+                ) {
+                    self.rst_info.take();
+                    self.b_capturing = false;
+                }
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button("Select elf file").clicked() {
+                    let mut dialog = FileDialog::open_file(self.selected_file.clone());
+                    dialog.open();
+                    self.file_dialog = Some(dialog);
+                }
+                ui.label(format!("Selected elf file: {:?}", self.selected_file));
+            });
+            if let Some(dialog) = &mut self.file_dialog {
+                if dialog.show(ctx).selected() {
+                    if let Some(file) = dialog.path() {
+                        self.selected_file = Some(file.to_path_buf());
+                        match ElfSymbols::load(file) {
+                            Ok(symbols) => self.elf_symbols = symbols,
+                            Err(e) => {
+                                self.elf_symbols = ElfSymbols::default();
+                                self.rst_info = Some(format!("{}", e));
+                            }
+                        }
+                    }
+                }
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if let Some(h) = self.probe_rs_handler.borrow_mut() {
+                    eframe::egui::ComboBox::from_label("core")
+                        .selected_text(format!("{}", self.cur_target_core_idx))
+                        .show_ui(ui, |ui| {
+                            for c in 0..h.target_cores_num {
+                                ui.selectable_value(
+                                    &mut self.cur_target_core_idx,
+                                    c,
+                                    format!("{}", c),
+                                );
+                            }
+                        });
+                    ui.label("trace clock (Hz)");
+                    ui.add_enabled(
+                        !self.b_capturing,
+                        eframe::egui::DragValue::new(&mut self.trace_clock_hz).range(1..=u32::MAX),
+                    );
+                    ui.label("SWO baud");
+                    ui.add_enabled(
+                        !self.b_capturing,
+                        eframe::egui::DragValue::new(&mut self.swo_baud).range(1..=u32::MAX),
+                    );
+                    ui.add_enabled(
+                        !self.b_capturing,
+                        eframe::egui::Checkbox::new(&mut self.b_pc_sampling, "pc sampling"),
+                    );
+                    if !self.b_capturing {
+                        if ui.button("start").clicked() {
+                            match h.setup_swo(
+                                self.cur_target_core_idx,
+                                self.trace_clock_hz,
+                                self.swo_baud,
+                                self.b_pc_sampling,
+                            ) {
+                                Ok(_) => {
+                                    self.b_capturing = true;
+                                    self.rst_info.take();
+                                }
+                                Err(e) => self.rst_info = Some(format!("{}", e)),
+                            }
+                        }
+                    } else if ui.button("stop").clicked() {
+                        self.b_capturing = false;
+                        if let Err(e) = h.stop_swo(self.cur_target_core_idx) {
+                            self.rst_info = Some(format!("{}", e));
+                        }
+                    }
+                }
+                if ui.button("clear").clicked() {
+                    self.clear();
+                }
+            });
+            ui.label(format!(
+                "{} bytes received, {} overflows, {} malformed",
+                self.n_bytes, self.n_overflows, self.decoder.n_malformed
+            ));
+            if let Some(info) = self.rst_info.as_ref() {
+                ui.label(info);
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                for port in self.ports.keys() {
+                    ui.selectable_value(
+                        &mut self.panel,
+                        Some(TracePanel::Port(*port)),
+                        format!("port {}", port),
+                    );
+                }
+                ui.selectable_value(&mut self.panel, Some(TracePanel::Profile), "Profile");
+            });
+            match self.panel {
+                Some(TracePanel::Port(port)) => {
+                    if let Some(stream) = self.ports.get(&port) {
+                        eframe::egui::ScrollArea::vertical()
+                            .auto_shrink(false)
+                            .stick_to_bottom(true)
+                            .show(ui, |ui| {
+                                for l in stream.lines.iter() {
+                                    ui.monospace(l);
+                                }
+                                if !stream.pending.is_empty() {
+                                    ui.monospace(String::from_utf8_lossy(&stream.pending));
+                                }
+                            });
+                    }
+                }
+                Some(TracePanel::Profile) => self.profile_ui(ui),
+                None => {}
+            }
+        }
+    }
+}
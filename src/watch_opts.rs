@@ -0,0 +1,388 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_watch_opts {
+    use crate::configuration::m_config;
+    use crate::dwarf_info::m_dwarf_info::{DwarfInfo, DwarfType, TypeId};
+    use crate::elf_symbols::m_elf_symbols::ElfSymbols;
+    use crate::probe_rs_invoke::probe_rs_integration::ProbeRsHandler;
+    use std::{
+        collections::HashMap,
+        time::{Duration, Instant},
+    };
+
+    fn le_value(bytes: &[u8]) -> u128 {
+        let mut b = [0u8; 16];
+        let n = bytes.len().min(16);
+        b[..n].copy_from_slice(&bytes[..n]);
+        u128::from_le_bytes(b)
+    }
+
+    fn sign_extend(value: u128, size: usize) -> i128 {
+        if size == 0 || size >= 16 {
+            return value as i128;
+        }
+        let shift = 128 - size * 8;
+        ((value << shift) as i128) >> shift
+    }
+
+    /// Renders typed target memory as an expandable tree.
+    pub struct ValueRenderer<'a> {
+        pub dwarf: &'a DwarfInfo,
+        pub symbols: &'a ElfSymbols,
+        /// Reads target memory for dereferenced pointers.
+        pub read: &'a mut dyn FnMut(u64, usize) -> Option<Vec<u8>>,
+    }
+
+    impl ValueRenderer<'_> {
+        /// One line summary of a value, as shown next to its name.
+        pub fn value_text(&self, type_id: Option<TypeId>, bytes: &[u8]) -> String {
+            let size = self.dwarf.type_size(type_id) as usize;
+            if bytes.len() < size {
+                return "<unavailable>".to_owned();
+            }
+            let bytes = &bytes[..size];
+            match self.dwarf.resolve_type(type_id) {
+                Some(DwarfType::Base { encoding, .. }) => {
+                    let v = le_value(bytes);
+                    match *encoding {
+                        gimli::DW_ATE_float if size == 4 => {
+                            format!("{}", f32::from_bits(v as u32))
+                        }
+                        gimli::DW_ATE_float if size == 8 => {
+                            format!("{}", f64::from_bits(v as u64))
+                        }
+                        gimli::DW_ATE_boolean => format!("{}", v != 0),
+                        gimli::DW_ATE_signed_char | gimli::DW_ATE_unsigned_char => {
+                            let c = v as u8;
+                            if c.is_ascii_graphic() || c == b' ' {
+                                format!("{} '{}'", v, c as char)
+                            } else {
+                                format!("{}", v)
+                            }
+                        }
+                        gimli::DW_ATE_UTF => char::from_u32(v as u32)
+                            .map_or(format!("{:#x}", v), |c| format!("{:?}", c)),
+                        gimli::DW_ATE_signed => {
+                            format!("{} ({:#x})", sign_extend(v, size), v)
+                        }
+                        _ => format!("{} ({:#x})", v, v),
+                    }
+                }
+                Some(DwarfType::Enum { variants, size, .. }) => {
+                    let v = sign_extend(le_value(bytes), *size as usize);
+                    match variants.iter().find(|(_, value)| *value == v) {
+                        Some((name, _)) => format!("{} ({})", name, v),
+                        None => format!("{}", v),
+                    }
+                }
+                Some(DwarfType::Pointer { .. }) => {
+                    let v = le_value(bytes) as u64;
+                    match self.symbols.symbol_at(v) {
+                        Some((s, 0)) => format!("{:#010x} <{}>", v, s.name),
+                        Some((s, o)) => format!("{:#010x} <{}+{:#x}>", v, s.name, o),
+                        None => format!("{:#010x}", v),
+                    }
+                }
+                Some(DwarfType::Array { elem, .. }) => {
+                    let b_char = matches!(
+                        self.dwarf.resolve_type(*elem),
+                        Some(DwarfType::Base { size: 1, encoding, .. })
+                            if *encoding == gimli::DW_ATE_signed_char
+                                || *encoding == gimli::DW_ATE_unsigned_char
+                    );
+                    if b_char {
+                        let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+                        format!("{:?}", String::from_utf8_lossy(&bytes[..end]))
+                    } else {
+                        "[...]".to_owned()
+                    }
+                }
+                Some(DwarfType::Struct { .. }) => "{...}".to_owned(),
+                _ => bytes
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            }
+        }
+
+        pub fn ui(
+            &mut self,
+            ui: &mut eframe::egui::Ui,
+            label: &str,
+            id_path: &str,
+            type_id: Option<TypeId>,
+            bytes: &[u8],
+        ) {
+            let dwarf = self.dwarf;
+            let header = format!(
+                "{}: {} = {}",
+                label,
+                dwarf.type_name(type_id),
+                self.value_text(type_id, bytes)
+            );
+            match dwarf.resolve_type(type_id) {
+                Some(DwarfType::Struct { members, .. }) if !members.is_empty() => {
+                    eframe::egui::CollapsingHeader::new(header)
+                        .id_salt(id_path)
+                        .show(ui, |ui| {
+                            for m in members {
+                                let start = (m.offset as usize).min(bytes.len());
+                                self.ui(
+                                    ui,
+                                    &m.name,
+                                    &format!("{}.{}", id_path, m.name),
+                                    m.type_id,
+                                    &bytes[start..],
+                                );
+                            }
+                        });
+                }
+                Some(DwarfType::Array { elem, count }) => {
+                    let elem_size = dwarf.type_size(*elem) as usize;
+                    let count = count.unwrap_or(0) as usize;
+                    eframe::egui::CollapsingHeader::new(header)
+                        .id_salt(id_path)
+                        .show(ui, |ui| {
+                            for i in 0..count.min(m_config::WATCH_ARRAY_ELEMENTS_SHOWN) {
+                                let start = (i * elem_size).min(bytes.len());
+                                self.ui(
+                                    ui,
+                                    &format!("[{}]", i),
+                                    &format!("{}[{}]", id_path, i),
+                                    *elem,
+                                    &bytes[start..],
+                                );
+                            }
+                            if count > m_config::WATCH_ARRAY_ELEMENTS_SHOWN {
+                                ui.label(format!(
+                                    "... {} more",
+                                    count - m_config::WATCH_ARRAY_ELEMENTS_SHOWN
+                                ));
+                            }
+                        });
+                }
+                Some(DwarfType::Pointer { target, size, .. })
+                    if bytes.len() >= *size as usize && dwarf.type_size(*target) > 0 =>
+                {
+                    let address = le_value(&bytes[..*size as usize]) as u64;
+                    let target_size = dwarf.type_size(*target) as usize;
+                    eframe::egui::CollapsingHeader::new(header)
+                        .id_salt(id_path)
+                        .show(ui, |ui| match (self.read)(address, target_size) {
+                            Some(target_bytes) => {
+                                self.ui(
+                                    ui,
+                                    &format!("*{}", label),
+                                    &format!("{}*", id_path),
+                                    *target,
+                                    &target_bytes,
+                                );
+                            }
+                            None => {
+                                ui.label(format!("can't read {:#010x}", address));
+                            }
+                        });
+                }
+                _ => {
+                    ui.label(header);
+                }
+            }
+        }
+    }
+
+    struct WatchEntry {
+        name: String,
+        address: u64,
+        type_id: Option<TypeId>,
+        bytes: Vec<u8>,
+        err: Option<String>,
+    }
+
+    #[derive(Default)]
+    pub struct WatchView {
+        entries: Vec<WatchEntry>,
+        new_name: String,
+        b_auto_refresh: bool,
+        refresh_interval_ms: u64,
+        last_refresh: Option<Instant>,
+        deref_cache: HashMap<(u64, usize), Option<Vec<u8>>>,
+        rst_info: Option<String>,
+    }
+
+    impl WatchView {
+        fn refresh(&mut self, h: &mut ProbeRsHandler, core_idx: usize, dwarf: &DwarfInfo) {
+            self.last_refresh = Some(Instant::now());
+            self.deref_cache.clear();
+            for e in self.entries.iter_mut() {
+                let size = (dwarf.type_size(e.type_id) as usize).min(m_config::WATCH_MAX_READ);
+                let mut buf = vec![0u8; size];
+                match h.read_memory(core_idx, e.address, 1, &mut buf) {
+                    Ok(_) => {
+                        e.bytes = buf;
+                        e.err = None;
+                    }
+                    Err(err) => {
+                        e.err = Some(format!("{}", err));
+                    }
+                }
+            }
+        }
+
+        fn add(&mut self, dwarf: &DwarfInfo) {
+            match dwarf.resolve_expression(&self.new_name) {
+                Ok((name, address, type_id)) => {
+                    self.entries.push(WatchEntry {
+                        name,
+                        address,
+                        type_id,
+                        bytes: Vec::new(),
+                        err: None,
+                    });
+                    self.new_name.clear();
+                    self.rst_info.take();
+                    // Read the new entry right away.
+                    self.last_refresh = None;
+                }
+                Err(e) => {
+                    self.rst_info = Some(e);
+                }
+            }
+        }
+
+        pub fn ui(
+            &mut self,
+            ui: &mut eframe::egui::Ui,
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            symbols: &ElfSymbols,
+            dwarf: &DwarfInfo,
+        ) {
+            if self.refresh_interval_ms == 0 {
+                self.refresh_interval_ms = m_config::WATCH_REFRESH_MS;
+                self.b_auto_refresh = true;
+            }
+
+            ui.horizontal(|ui| {
+                let resp = ui.add(
+                    eframe::egui::TextEdit::singleline(&mut self.new_name)
+                        .hint_text("global, e.g. foo.bar[3]")
+                        .desired_width(200.0),
+                );
+                let b_enter =
+                    resp.lost_focus() && ui.input(|i| i.key_pressed(eframe::egui::Key::Enter));
+                let filter = self.new_name.trim().to_owned();
+                eframe::egui::ComboBox::from_id_salt("watch_globals")
+                    .selected_text("globals")
+                    .show_ui(ui, |ui| {
+                        for g in dwarf
+                            .globals()
+                            .iter()
+                            .filter(|g| g.scoped_name.contains(&filter))
+                            .take(m_config::WATCH_VARIABLES_SHOWN)
+                        {
+                            if ui.selectable_label(false, &g.scoped_name).clicked() {
+                                self.new_name = g.scoped_name.clone();
+                            }
+                        }
+                    });
+                if (ui.button("add watch").clicked() || b_enter) && !self.new_name.is_empty() {
+                    self.add(dwarf);
+                }
+                if ui.button("refresh").clicked() {
+                    self.refresh(h, core_idx, dwarf);
+                }
+                ui.checkbox(&mut self.b_auto_refresh, "auto refresh");
+                ui.add_enabled(
+                    self.b_auto_refresh,
+                    eframe::egui::Slider::new(&mut self.refresh_interval_ms, 50..=5000)
+                        .logarithmic(true)
+                        .text("interval (ms)"),
+                );
+            });
+            if dwarf.globals().is_empty() {
+                ui.label("select an elf file with debug info to watch variables");
+            }
+            if let Some(info) = self.rst_info.as_ref() {
+                ui.label(info);
+            }
+
+            let refresh_interval = Duration::from_millis(self.refresh_interval_ms);
+            if self.last_refresh.is_none()
+                || (self.b_auto_refresh
+                    && self
+                        .last_refresh
+                        .is_some_and(|t| t.elapsed() >= refresh_interval))
+            {
+                self.refresh(h, core_idx, dwarf);
+            }
+            if self.b_auto_refresh {
+                ui.ctx().request_repaint_after(refresh_interval);
+            }
+
+            let cache = &mut self.deref_cache;
+            let mut read = |address: u64, size: usize| -> Option<Vec<u8>> {
+                cache
+                    .entry((address, size))
+                    .or_insert_with(|| {
+                        let mut buf = vec![0u8; size.min(m_config::WATCH_MAX_READ)];
+                        h.read_memory(core_idx, address, 1, &mut buf)
+                            .ok()
+                            .map(|_| buf)
+                    })
+                    .clone()
+            };
+            let mut renderer = ValueRenderer {
+                dwarf,
+                symbols,
+                read: &mut read,
+            };
+            let mut remove_idx = None;
+            eframe::egui::ScrollArea::vertical()
+                .auto_shrink(false)
+                .show(ui, |ui| {
+                    for (i, e) in self.entries.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui.small_button("x").on_hover_text("remove").clicked() {
+                                remove_idx = Some(i);
+                            }
+                            ui.monospace(format!("{:#010x}", e.address));
+                            ui.vertical(|ui| match e.err.as_ref() {
+                                Some(err) => {
+                                    ui.label(format!("{}: {}", e.name, err));
+                                }
+                                None => {
+                                    renderer.ui(
+                                        ui,
+                                        &e.name,
+                                        &format!("watch_{}", e.name),
+                                        e.type_id,
+                                        &e.bytes,
+                                    );
+                                }
+                            });
+                        });
+                    }
+                });
+            if let Some(i) = remove_idx {
+                self.entries.remove(i);
+            }
+        }
+    }
+}
@@ -0,0 +1,357 @@
+/*
+ *  Simple GUI for probe-rs with egui framework.
+ *  Copyright (C) 2026 Joker2770
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod m_watchpoint_opts {
+    use crate::breakpoint_opts::m_breakpoint_opts::{describe_address, resolve_location};
+    use crate::configuration::m_config;
+    use crate::dwarf_info::m_dwarf_info::DwarfInfo;
+    use crate::elf_symbols::m_elf_symbols::ElfSymbols;
+    use crate::probe_rs_invoke::probe_rs_integration::{ProbeRsHandler, WatchpointAccess};
+    use probe_rs::{CoreStatus, HaltReason};
+
+    const ACCESS_KINDS: [(WatchpointAccess, &str); 3] = [
+        (WatchpointAccess::Write, "write"),
+        (WatchpointAccess::Read, "read"),
+        (WatchpointAccess::ReadWrite, "access"),
+    ];
+
+    fn access_text(access: WatchpointAccess) -> &'static str {
+        ACCESS_KINDS
+            .iter()
+            .find(|(a, _)| *a == access)
+            .map_or("", |(_, t)| t)
+    }
+
+    struct Watchpoint {
+        core_idx: usize,
+        location: String,
+        address: u64,
+        size: u32,
+        access: WatchpointAccess,
+        unit: Option<usize>,
+        hits: usize,
+    }
+
+    #[derive(Default)]
+    pub struct WatchpointView {
+        watchpoints: Vec<Watchpoint>,
+        new_location: String,
+        new_size: u32,
+        new_access: WatchpointAccess,
+        /// Comparators of a core, cached per session and core index.
+        units: Option<(usize, usize, u32)>,
+        /// Session the watchpoint units were programmed under.
+        session_id: usize,
+        cores_halted: Vec<bool>,
+        alerts: Vec<String>,
+        rst_info: Option<String>,
+    }
+
+    impl WatchpointView {
+        /// The comparators are lost with the session, watchpoints programmed under
+        /// another one are shown disabled again.
+        fn sync_session(&mut self, h: &ProbeRsHandler) {
+            if h.session.is_none() || h.session_id != self.session_id {
+                for w in self.watchpoints.iter_mut() {
+                    w.unit = None;
+                }
+                self.session_id = h.session_id;
+            }
+        }
+
+        /// Counts hits on the cores that just halted on a watchpoint. Returns true when
+        /// one of ours fired, so the caller can bring the Debug page up.
+        pub fn poll(
+            &mut self,
+            h: &mut ProbeRsHandler,
+            cores_status: &[Option<CoreStatus>],
+            symbols: &ElfSymbols,
+            dwarf: &DwarfInfo,
+        ) -> bool {
+            self.sync_session(h);
+            let mut b_fired = false;
+            self.cores_halted.resize(cores_status.len(), false);
+            for (c, status) in cores_status.iter().enumerate() {
+                let b_halted = status.is_some_and(|s| s.is_halted());
+                let b_new_halt = b_halted && !self.cores_halted[c];
+                self.cores_halted[c] = b_halted;
+                if !b_new_halt
+                    || !matches!(
+                        status,
+                        Some(CoreStatus::Halted(
+                            HaltReason::Watchpoint | HaltReason::Multiple
+                        ))
+                    )
+                {
+                    continue;
+                }
+                let pc = h.read_pc(c).ok().flatten();
+                for w in self.watchpoints.iter_mut() {
+                    let Some(unit) = w.unit else {
+                        continue;
+                    };
+                    if w.core_idx != c || !h.watchpoint_matched(c, unit).unwrap_or(false) {
+                        continue;
+                    }
+                    w.hits = w.hits.wrapping_add(1);
+                    let mut alert = format!(
+                        "core {} halted on {} watchpoint {} ({:#010x})",
+                        c,
+                        access_text(w.access),
+                        w.location,
+                        w.address
+                    );
+                    if let Some(pc) = pc {
+                        alert = format!(
+                            "{}, pc {:#010x} {}",
+                            alert,
+                            pc,
+                            describe_address(pc, symbols, dwarf)
+                        );
+                    }
+                    self.alerts.push(alert);
+                    b_fired = true;
+                }
+            }
+            b_fired
+        }
+
+        fn free_unit(&self, core_idx: usize) -> Option<usize> {
+            let (_, c, units) = self.units?;
+            if c != core_idx {
+                return None;
+            }
+            (0..units as usize).find(|u| {
+                !self
+                    .watchpoints
+                    .iter()
+                    .any(|w| w.core_idx == core_idx && w.unit == Some(*u))
+            })
+        }
+
+        fn enable(&mut self, h: &mut ProbeRsHandler, idx: usize, b_enable: bool) {
+            let Some(w) = self.watchpoints.get(idx) else {
+                return;
+            };
+            let core_idx = w.core_idx;
+            let rst = if b_enable {
+                let Some(unit) = self.free_unit(core_idx) else {
+                    self.rst_info = Some(match self.units {
+                        Some((_, _, u)) => format!("all {} DWT comparators are in use", u),
+                        None => "attach to the target first".to_owned(),
+                    });
+                    return;
+                };
+                h.set_watchpoint(core_idx, unit, w.address, w.size, w.access)
+                    .map(|_| Some(unit))
+            } else {
+                match w.unit {
+                    Some(unit) => h.clear_watchpoint(core_idx, unit).map(|_| None),
+                    None => Ok(None),
+                }
+            };
+            match rst {
+                Ok(unit) => {
+                    self.watchpoints[idx].unit = unit;
+                    self.rst_info.take();
+                }
+                Err(e) => {
+                    self.rst_info = Some(format!("{}", e));
+                }
+            }
+        }
+
+        fn add(&mut self, h: &mut ProbeRsHandler, core_idx: usize, address: u64) {
+            if !address.is_multiple_of(self.new_size as u64) {
+                self.rst_info = Some(format!(
+                    "{:#010x} is not aligned to {} bytes",
+                    address, self.new_size
+                ));
+                return;
+            }
+            self.watchpoints.push(Watchpoint {
+                core_idx,
+                location: self.new_location.trim().to_owned(),
+                address,
+                size: self.new_size,
+                access: self.new_access,
+                unit: None,
+                hits: 0,
+            });
+            self.new_location.clear();
+            self.enable(h, self.watchpoints.len() - 1, true);
+        }
+
+        pub fn ui(
+            &mut self,
+            ui: &mut eframe::egui::Ui,
+            h: &mut ProbeRsHandler,
+            core_idx: usize,
+            symbols: &ElfSymbols,
+            dwarf: &DwarfInfo,
+        ) {
+            if self.new_size == 0 {
+                self.new_size = 4;
+            }
+            self.sync_session(h);
+            if h.session.is_none() {
+                self.units = None;
+            } else if self
+                .units
+                .is_none_or(|(s, c, _)| s != h.session_id || c != core_idx)
+            {
+                match h.watchpoint_units(core_idx) {
+                    Ok(u) => self.units = u.map(|u| (h.session_id, core_idx, u)),
+                    Err(e) => {
+                        // Don't ask again every frame, this core has no DWT.
+                        self.units = Some((h.session_id, core_idx, 0));
+                        self.rst_info = Some(format!("{}", e));
+                    }
+                }
+            }
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    eframe::egui::TextEdit::singleline(&mut self.new_location)
+                        .hint_text("address or variable")
+                        .desired_width(200.0),
+                );
+                let filter = self.new_location.trim().to_owned();
+                eframe::egui::ComboBox::from_id_salt("watch_variables")
+                    .selected_text("variables")
+                    .show_ui(ui, |ui| {
+                        for v in symbols
+                            .variables()
+                            .filter(|v| v.name.contains(&filter))
+                            .take(m_config::WATCH_VARIABLES_SHOWN)
+                        {
+                            if ui
+                                .selectable_label(false, format!("{} ({} bytes)", v.name, v.size))
+                                .clicked()
+                            {
+                                self.new_location = v.name.clone();
+                                if matches!(v.size, 1 | 2 | 4) {
+                                    self.new_size = v.size as u32;
+                                }
+                            }
+                        }
+                    });
+                eframe::egui::ComboBox::from_label("size")
+                    .selected_text(format!("{}", self.new_size))
+                    .show_ui(ui, |ui| {
+                        for s in [1, 2, 4] {
+                            ui.selectable_value(&mut self.new_size, s, format!("{}", s));
+                        }
+                    });
+                eframe::egui::ComboBox::from_label("on")
+                    .selected_text(access_text(self.new_access))
+                    .show_ui(ui, |ui| {
+                        for (a, t) in ACCESS_KINDS {
+                            ui.selectable_value(&mut self.new_access, a, t);
+                        }
+                    });
+                if ui.button("add watchpoint").clicked() && !self.new_location.is_empty() {
+                    match resolve_location(&self.new_location, symbols, dwarf) {
+                        Ok(address) => self.add(h, core_idx, address),
+                        Err(e) => self.rst_info = Some(e),
+                    }
+                }
+                let used = self
+                    .watchpoints
+                    .iter()
+                    .filter(|w| w.core_idx == core_idx && w.unit.is_some())
+                    .count();
+                match self.units {
+                    Some((_, _, units)) => ui.label(format!("comparators: {}/{}", used, units)),
+                    None => ui.label("comparators: -"),
+                };
+            });
+            if let Some(info) = self.rst_info.as_ref() {
+                ui.label(info);
+            }
+
+            let mut toggle = None;
+            let mut remove_idx = None;
+            eframe::egui::ScrollArea::vertical()
+                .auto_shrink(false)
+                .show(ui, |ui| {
+                    eframe::egui::Grid::new("debug_watchpoints")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label("");
+                            ui.label("location");
+                            ui.label("address");
+                            ui.label("size");
+                            ui.label("on");
+                            ui.label("hits");
+                            ui.label("");
+                            ui.end_row();
+                            for (i, w) in self.watchpoints.iter().enumerate() {
+                                if w.core_idx != core_idx {
+                                    continue;
+                                }
+                                let mut enabled = w.unit.is_some();
+                                if ui.checkbox(&mut enabled, "").changed() {
+                                    toggle = Some((i, enabled));
+                                }
+                                ui.label(&w.location);
+                                ui.monospace(format!("{:#010x}", w.address));
+                                ui.label(format!("{}", w.size));
+                                ui.label(access_text(w.access));
+                                ui.label(format!("{}", w.hits));
+                                if ui.button("remove").clicked() {
+                                    remove_idx = Some(i);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+
+            if let Some((i, b_enable)) = toggle {
+                self.enable(h, i, b_enable);
+            }
+            if let Some(i) = remove_idx {
+                self.enable(h, i, false);
+                if self.watchpoints[i].unit.is_none() {
+                    self.watchpoints.remove(i);
+                }
+            }
+        }
+
+        pub fn alert_ui(&mut self, ctx: &eframe::egui::Context) {
+            if self.alerts.is_empty() {
+                return;
+            }
+            let mut b_dismiss = false;
+            eframe::egui::Window::new("Watchpoint")
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    for a in self.alerts.iter() {
+                        ui.colored_label(ui.visuals().warn_fg_color, a);
+                    }
+                    if ui.button("dismiss").clicked() {
+                        b_dismiss = true;
+                    }
+                });
+            if b_dismiss {
+                self.alerts.clear();
+            }
+        }
+    }
+}